@@ -1,4 +1,4 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, generator = generate_input);
 
 fn parse(input: &str) -> Vec<i16> {
     input
@@ -46,6 +46,17 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(password as u64)
 }
 
+/// Generates `size` rotations of up to 999 clicks for `--scale`.
+pub fn generate_input(size: usize) -> String {
+    let mut rng = advent_of_code::template::Rng::new(1);
+    (0..size)
+        .map(|_| {
+            let direction = if rng.range(0..2) == 0 { 'L' } else { 'R' };
+            format!("{direction}{}\n", rng.range(1..1000))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,105 +1,87 @@
+use advent_of_code::{intervals::IntervalSet, parse};
+
 advent_of_code::solution!(2);
 
-const POW10: [u64; 11] = [
-    1,
-    10,
-    100,
-    1_000,
-    10_000,
-    100_000,
-    1_000_000,
-    10_000_000,
-    100_000_000,
-    1_000_000_000,
-    10_000_000_000,
-];
-
-fn num_digits(n: u64) -> u32 {
-    n.ilog10() + 1
+fn parse_ranges(input: &str) -> Option<IntervalSet> {
+    let ranges = parse::ranges(input).ok()?;
+    Some(ranges.into_iter().map(|(start, end)| start..=end).collect())
 }
 
-fn has_pattern_repeated_twice(n: u64) -> bool {
-    let len = num_digits(n);
-
-    if !len.is_multiple_of(2) {
-        return false;
-    }
-
-    let half = len / 2;
-    let pow10_half = POW10[half as usize];
-
-    let hi = n / pow10_half; // first half
-    let lo = n % pow10_half; // second half
-
-    hi == lo
+fn get_digit_count(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
 }
 
-fn has_repeating_pattern(n: u64) -> bool {
-    let len = num_digits(n) as usize;
-    let pow10_len = POW10[len]; // assuming POW10[i] = 10^i as u64
-
-    for sub_len in 1..=len / 2 {
-        if !len.is_multiple_of(sub_len) {
-            continue;
-        }
-
-        let pow10_sub = POW10[sub_len];
-
-        // First sub_len digits as the pattern
-        let pattern = n / POW10[len - sub_len];
-
-        // Geometric series: 1 + r + r^2 + ... + r^(k-1),
-        // where r = 10^sub_len and k = len / sub_len.
-        let multiplier = (pow10_len - 1) / (pow10_sub - 1);
-
-        // Avoid overflow just in case (even though any valid n must fit in u64)
-        if let Some(acc) = pattern.checked_mul(multiplier)
-            && acc == n
-        {
-            return true;
-        }
+/// Returns the sum of the IDs in `start..=end` with `len` digits which consist of a digit
+/// pattern of `pattern_len` digits repeated, such as `123123` for 6 and 3 digits.
+fn sum_repeated_ids(start: u64, end: u64, len: u32, pattern_len: u32) -> u128 {
+    // Repeating a pattern is a multiplication with 1, 1001, 1001001, etc.
+    let multiplier = (10u128.pow(len) - 1) / (10u128.pow(pattern_len) - 1);
+    let first = u128::from(start)
+        .max(10u128.pow(len - 1))
+        .div_ceil(multiplier)
+        .max(10u128.pow(pattern_len - 1));
+    let last = u128::from(end).min(10u128.pow(len) - 1) / multiplier;
+
+    if first > last {
+        return 0;
     }
-
-    false
+    multiplier * (first + last) * (last - first + 1) / 2
 }
 
-fn parse_range(range: &str) -> Option<(u64, u64)> {
-    let mut parts = range.split('-').map(|s| s.parse::<u64>());
-    let start = parts.next()?.ok()?;
-    let end = parts.next()?.ok()?;
-    Some((start, end))
-}
-
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut invalid_sum: u64 = 0;
-
-    for range in input.trim().split(',') {
-        let (start, end) = parse_range(range)?;
-
-        for n in start..=end {
-            if has_pattern_repeated_twice(n) {
-                invalid_sum += n;
+/// The Möbius function, for the inclusion-exclusion over the pattern lengths.
+fn mobius(mut n: u32) -> i128 {
+    let mut result = 1;
+    let mut factor = 2;
+    while n > 1 {
+        if n.is_multiple_of(factor) {
+            n /= factor;
+            if n.is_multiple_of(factor) {
+                return 0;
             }
+            result = -result;
         }
+        factor += 1;
     }
+    result
+}
 
-    Some(invalid_sum)
+pub fn part_one(input: &str) -> Option<u64> {
+    let ranges = parse_ranges(input)?;
+    let sum: u128 = ranges
+        .iter()
+        .flat_map(|range| {
+            let (start, end) = range.into_inner();
+            (get_digit_count(start)..=get_digit_count(end))
+                .filter(|len| len.is_multiple_of(2))
+                .map(move |len| sum_repeated_ids(start, end, len, len / 2))
+        })
+        .sum();
+
+    u64::try_from(sum).ok()
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut invalid_sum: u64 = 0;
-
-    for range in input.trim().split(',') {
-        let (start, end) = parse_range(range)?;
-
-        for n in start..=end {
-            if has_repeating_pattern(n) {
-                invalid_sum += n;
-            }
-        }
-    }
-
-    Some(invalid_sum)
+    let ranges = parse_ranges(input)?;
+
+    // An ID can be a repetition of several pattern lengths, e.g. `111111` of 1, 2 and 3.
+    // Its pattern lengths are multiples of its shortest one, which the Möbius function
+    // weighs so that every ID is counted exactly once.
+    let sum: i128 = ranges
+        .iter()
+        .flat_map(|range| {
+            let (start, end) = range.into_inner();
+            (get_digit_count(start)..=get_digit_count(end)).flat_map(move |len| {
+                (1..len)
+                    .filter(move |&pattern_len| len.is_multiple_of(pattern_len))
+                    .map(move |pattern_len| {
+                        let sum = sum_repeated_ids(start, end, len, pattern_len) as i128;
+                        -mobius(len / pattern_len) * sum
+                    })
+            })
+        })
+        .sum();
+
+    u64::try_from(sum).ok()
 }
 
 #[cfg(test)]
@@ -117,4 +99,34 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(4174379265));
     }
+
+    #[test]
+    fn test_repeated_ids() {
+        // 111111 repeats patterns of 1, 2 and 3 digits, but is counted once.
+        assert_eq!(part_two("111110-111112"), Some(111111));
+        assert_eq!(part_one("111110-111112"), Some(111111));
+        assert_eq!(part_one("1212-1313,99-101"), Some(1212 + 1313 + 99));
+
+        // IDs close to `u64::MAX`, which has 20 digits.
+        assert_eq!(
+            part_one("9999999999-10000000000,10000000001000000000-10000000001000000000"),
+            Some(9999999999 + 10000000001000000000)
+        );
+        assert_eq!(
+            part_two("11111111111111111111-11111111111111111111"),
+            Some(11111111111111111111)
+        );
+    }
+
+    #[test]
+    fn test_mobius() {
+        let values: Vec<i128> = (1..=12).map(mobius).collect();
+        assert_eq!(values, [1, -1, -1, 0, -1, 1, -1, 0, 0, 1, -1, 0]);
+    }
+
+    #[test]
+    fn test_ranges_ending_at_zero() {
+        assert_eq!(part_one("0-0"), Some(0));
+        assert_eq!(part_two("0-0"), Some(0));
+    }
 }
@@ -1,76 +1,37 @@
-advent_of_code::solution!(4);
-
-// count all 8 directions for "@"
-const NEIGHBORS: [(isize, isize); 8] = [
-    (-1, -1),
-    (-1, 0),
-    (-1, 1),
-    (0, -1),
-    (0, 1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-];
+use advent_of_code::grid::Grid;
+use glam::IVec2;
 
-fn is_accessible(map: &[Vec<char>], x: usize, y: usize) -> bool {
-    // if fewer than 4 direcitons have "@" return true else false
-    let mut count = 0;
-    for (dx, dy) in NEIGHBORS.iter() {
-        let nx = x as isize + dx;
-        let ny = y as isize + dy;
-        if nx >= 0
-            && ny >= 0
-            && (nx as usize) < map.len()
-            && (ny as usize) < map[0].len()
-            && map[ny as usize][nx as usize] == '@'
-        {
-            count += 1;
-        }
+advent_of_code::solution!(4);
 
-        if count > 4 {
-            return false;
-        }
-    }
-    count < 4
+fn is_accessible(map: &Grid<char>, pos: IVec2) -> bool {
+    // accessible if fewer than 4 of the 8 neighbors have "@"
+    map.neighbors8(pos).filter(|n| map[*n] == '@').count() < 4
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let map = input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>();
-
-    let mut accessible_positions = 0;
+    let map = Grid::parse(input).ok()?;
 
-    for y in 0..map.len() {
-        for x in 0..map[0].len() {
-            if map[y][x] != '.' && is_accessible(&map, x, y) {
-                accessible_positions += 1;
-            }
-        }
-    }
+    let accessible_positions = map
+        .positions()
+        .filter(|pos| map[*pos] != '.' && is_accessible(&map, *pos))
+        .count();
 
-    Some(accessible_positions)
+    Some(accessible_positions as u64)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut map = input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>();
+    let mut map = Grid::parse(input).ok()?;
 
     let mut accessible_positions = 0;
 
     let mut changed = true;
     while changed {
         changed = false;
-        for y in 0..map.len() {
-            for x in 0..map[0].len() {
-                if map[y][x] != '.' && is_accessible(&map, x, y) {
-                    accessible_positions += 1;
-                    map[y][x] = '.';
-                    changed = true;
-                }
+        for pos in map.positions() {
+            if map[pos] != '.' && is_accessible(&map, pos) {
+                accessible_positions += 1;
+                map[pos] = '.';
+                changed = true;
             }
         }
     }
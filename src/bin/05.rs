@@ -1,67 +1,43 @@
-advent_of_code::solution!(5);
+use advent_of_code::{intervals::IntervalSet, parse};
+
+advent_of_code::solution!(5, generator = generate_input);
+
+fn parse_database(input: &str) -> Option<(IntervalSet, Vec<u64>)> {
+    let [ranges, ids] = parse::sections(input)[..] else {
+        return None;
+    };
+    let fresh = parse::ranges(ranges)
+        .ok()?
+        .into_iter()
+        .map(|(start, end)| start..=end)
+        .collect();
+    Some((fresh, parse::unsigned(ids).ok()?))
+}
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let mut id_ranges: Vec<(u64, u64)> = Vec::new();
-    let mut ids: Vec<u64> = Vec::new();
-
-    for line in input.lines() {
-        if line.contains('-') {
-            let parts: Vec<&str> = line.split('-').collect();
-            let start: u64 = parts[0].trim().parse().ok()?;
-            let end: u64 = parts[1].trim().parse().ok()?;
-            id_ranges.push((start, end));
-        } else if !line.is_empty() {
-            let id: u64 = line.trim().parse().ok()?;
-            ids.push(id);
-        }
-    }
-
-    let mut fresh_ids: Vec<u64> = Vec::new();
-
-    for id in ids {
-        for (start, end) in &id_ranges {
-            if id >= *start && id <= *end {
-                fresh_ids.push(id);
-                break;
-            }
-        }
-    }
-
-    Some(fresh_ids.len() as u64)
+    let (fresh, ids) = parse_database(input)?;
+    Some(ids.into_iter().filter(|&id| fresh.contains(id)).count() as u64)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut id_ranges: Vec<(u64, u64)> = Vec::new();
-
-    for line in input.lines() {
-        if line.contains('-') {
-            let parts: Vec<&str> = line.split('-').collect();
-            let start: u64 = parts[0].trim().parse().ok()?;
-            let end: u64 = parts[1].trim().parse().ok()?;
-            id_ranges.push((start, end));
-        }
-    }
+    let (fresh, _) = parse_database(input)?;
+    Some(fresh.len())
+}
 
-    let mut merged_ranges: Vec<(u64, u64)> = Vec::new();
-    id_ranges.sort_by_key(|k| k.0);
-    for range in id_ranges {
-        if let Some(last) = merged_ranges.last_mut() {
-            if range.0 <= last.1 + 1 {
-                last.1 = last.1.max(range.1);
-            } else {
-                merged_ranges.push(range);
-            }
-        } else {
-            merged_ranges.push(range);
-        }
+/// Generates `size` overlapping ranges followed by `size` ids for `--scale`.
+pub fn generate_input(size: usize) -> String {
+    let mut rng = advent_of_code::template::Rng::new(5);
+    let limit = 1 << 40;
+    let mut input = String::new();
+    for _ in 0..size {
+        let start = rng.range(1..limit);
+        input += &format!("{start}-{}\n", start + rng.range(0..limit / 100));
     }
-
-    let mut fresh_count: u64 = 0;
-    for range in merged_ranges {
-        fresh_count += range.1 - range.0 + 1;
+    input.push('\n');
+    for _ in 0..size {
+        input += &format!("{}\n", rng.range(1..limit));
     }
-
-    Some(fresh_count)
+    input
 }
 
 #[cfg(test)]
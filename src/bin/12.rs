@@ -1,30 +1,213 @@
+use std::iter;
+
+use advent_of_code::{
+    exact_cover::{Budget, ExactCover},
+    grid::Grid,
+    parse,
+};
+use glam::IVec2;
+
 advent_of_code::solution!(12);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut count = 0;
-    for line in input.lines() {
-        if !line.contains("x") {
+/// The present cells of one orientation of a shape, relative to its first cell in
+/// row-major order.
+type Orientation = Vec<IVec2>;
+
+struct Region {
+    width: usize,
+    height: usize,
+    counts: Vec<usize>,
+}
+
+fn parse_presents(input: &str) -> Option<(Vec<Vec<IVec2>>, Vec<Region>)> {
+    let mut shapes = Vec::new();
+    let mut regions = Vec::new();
+
+    for section in parse::sections(input) {
+        let (header, rest) = section.split_once('\n').unwrap_or((section, ""));
+        if header.ends_with(':') && !header.contains('x') {
+            let grid = Grid::parse(rest).ok()?;
+            shapes.push(grid.find_all(&'#').collect());
             continue;
         }
+        for line in section.lines() {
+            let [width, height, counts @ ..] = &parse::unsigned::<usize>(line).ok()?[..] else {
+                return None;
+            };
+            regions.push(Region {
+                width: *width,
+                height: *height,
+                counts: counts.to_vec(),
+            });
+        }
+    }
+
+    Some((shapes, regions))
+}
+
+/// Returns the distinct rotations and reflections of a shape.
+fn get_orientations(cells: &[IVec2]) -> Vec<Orientation> {
+    let mut orientations: Vec<Orientation> = Vec::new();
+    for flip in [false, true] {
+        for turns in 0..4 {
+            let mut orientation: Orientation = cells
+                .iter()
+                .map(|&cell| {
+                    let cell = if flip {
+                        IVec2::new(-cell.x, cell.y)
+                    } else {
+                        cell
+                    };
+                    (0..turns).fold(cell, |cell, _| cell.perp())
+                })
+                .collect();
+            orientation.sort_by_key(|cell| (cell.y, cell.x));
+            let anchor = orientation[0];
+            orientation.iter_mut().for_each(|cell| *cell -= anchor);
+
+            if !orientations.contains(&orientation) {
+                orientations.push(orientation);
+            }
+        }
+    }
+    orientations
+}
+
+/// The side of the smallest square that holds every shape in any orientation.
+fn get_box_size(shapes: &[Vec<IVec2>]) -> usize {
+    shapes
+        .iter()
+        .flatten()
+        .map(|&cell| cell.max_element())
+        .max()
+        .map_or(0, |max| max as usize + 1)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Verdict {
+    Fits,
+    DoesNotFit,
+    /// The search ran out of its node budget.
+    Unknown,
+}
+
+const NODE_BUDGET: u64 = 1_000_000;
+
+/// Returns the cells covered by every placement of an orientation within the region.
+fn get_placements(orientation: &Orientation, width: usize, height: usize) -> Vec<Vec<usize>> {
+    let size = IVec2::new(width as i32, height as i32);
+    (0..size.y)
+        .flat_map(|y| (0..size.x).map(move |x| IVec2::new(x, y)))
+        .filter_map(|pos| {
+            orientation
+                .iter()
+                .map(|&offset| {
+                    let cell = pos + offset;
+                    (cell.cmpge(IVec2::ZERO).all() && cell.cmplt(size).all())
+                        .then(|| cell.y as usize * width + cell.x as usize)
+                })
+                .collect()
+        })
+        .collect()
+}
 
-        let (size, regions) = line.split_once(": ").expect("region expected");
+/// Decides whether the presents fit into the region. The area and the disjoint
+/// bounding boxes decide most regions, the others are searched as an exact cover:
+/// every present is placed exactly once, every cell is covered at most once.
+fn get_verdict(
+    shapes: &[Vec<IVec2>],
+    orientations: &[Vec<Orientation>],
+    region: &Region,
+) -> Verdict {
+    let area = region.width * region.height;
+    let cells: usize = region
+        .counts
+        .iter()
+        .zip(shapes)
+        .map(|(count, shape)| count * shape.len())
+        .sum();
+    let presents: usize = region.counts.iter().sum();
+    if cells > area {
+        return Verdict::DoesNotFit;
+    }
 
-        let area = size
-            .split("x")
-            .filter_map(|n| n.parse::<u64>().ok())
-            .fold(1, |acc, x| acc * x);
+    let side = get_box_size(shapes);
+    if side > 0 && (region.width / side) * (region.height / side) >= presents {
+        return Verdict::Fits;
+    }
 
-        let size = regions
-            .split(" ")
-            .map(|n| n.parse::<u64>().unwrap() * 8)
-            .sum::<u64>();
+    // Copies of a shape are interchangeable, so the search only tries them in the order
+    // of their first cells. For consecutive copies, the earlier one covers a column for
+    // every set bit of its first cell and the later one for every unset bit, keyed by the
+    // bit and the bits above it: they share a column exactly if the later one starts first,
+    // and copies starting at the same cell already share that cell.
+    let pairs = presents - region.counts.iter().filter(|&&count| count > 0).count();
+    let bits = (usize::BITS - area.leading_zeros()) as usize;
+    let mut problem = ExactCover::new(presents, area * (1 + pairs * bits));
+    let cell_column = |cell: usize| presents + cell;
+    let order_column = |pair: usize, bit: usize, cell: usize| {
+        presents + area * (1 + pair * bits + bit) + (cell >> (bit + 1))
+    };
+    let order_columns = move |pair: usize, cell: usize, set: bool| {
+        (0..bits)
+            .filter(move |bit| (cell >> bit & 1 == 1) == set)
+            .map(move |bit| order_column(pair, bit, cell))
+    };
 
-        if size < area {
-            count += 1;
+    let (mut present, mut pair) = (0, 0);
+    for (shape, &count) in region.counts.iter().enumerate() {
+        let placements: Vec<Vec<usize>> = orientations[shape]
+            .iter()
+            .flat_map(|orientation| get_placements(orientation, region.width, region.height))
+            .collect();
+        for copy in 0..count {
+            for placement in &placements {
+                let first = placement[0];
+                let after_previous = (copy > 0).then(|| order_columns(pair - 1, first, false));
+                let before_next = (copy + 1 < count).then(|| order_columns(pair, first, true));
+                problem.add_row(
+                    iter::once(present)
+                        .chain(placement.iter().map(|&cell| cell_column(cell)))
+                        .chain(after_previous.into_iter().flatten())
+                        .chain(before_next.into_iter().flatten()),
+                );
+            }
+            present += 1;
+            if copy + 1 < count {
+                pair += 1;
+            }
         }
     }
+    problem.set_budget(Budget::nodes(NODE_BUDGET));
+
+    match problem.first_solution() {
+        Ok(Some(_)) => Verdict::Fits,
+        Ok(None) => Verdict::DoesNotFit,
+        Err(_) => Verdict::Unknown,
+    }
+}
 
-    Some(count)
+pub fn part_one(input: &str) -> Option<u64> {
+    let (shapes, regions) = parse_presents(input)?;
+    let orientations: Vec<Vec<Orientation>> =
+        shapes.iter().map(|shape| get_orientations(shape)).collect();
+
+    let verdicts: Vec<Verdict> = regions
+        .iter()
+        .map(|region| get_verdict(&shapes, &orientations, region))
+        .collect();
+
+    if verdicts.contains(&Verdict::Unknown) {
+        return None;
+    }
+    Some(
+        verdicts
+            .iter()
+            .filter(|&&verdict| verdict == Verdict::Fits)
+            .count() as u64,
+    )
 }
 
 pub fn part_two(_input: &str) -> Option<u64> {
@@ -35,12 +218,65 @@ pub fn part_two(_input: &str) -> Option<u64> {
 mod tests {
     use super::*;
 
+    /// The original estimate, which assumes every present needs 8 cells.
+    fn guess_fit(region: &Region) -> bool {
+        region.counts.iter().sum::<usize>() * 8 < region.width * region.height
+    }
+
+    /// Returns the indices of the regions the estimate gets wrong.
+    fn get_disagreements(regions: &[Region], verdicts: &[Verdict]) -> Vec<usize> {
+        regions
+            .iter()
+            .zip(verdicts)
+            .enumerate()
+            .filter(|(_, (region, verdict))| {
+                **verdict != Verdict::Unknown && guess_fit(region) != (**verdict == Verdict::Fits)
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(2));
     }
 
+    #[test]
+    fn test_example_verdicts() {
+        let (shapes, regions) =
+            parse_presents(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let orientations: Vec<Vec<Orientation>> =
+            shapes.iter().map(|shape| get_orientations(shape)).collect();
+        let verdicts: Vec<Verdict> = regions
+            .iter()
+            .map(|region| get_verdict(&shapes, &orientations, region))
+            .collect();
+        assert_eq!(
+            verdicts,
+            [Verdict::Fits, Verdict::Fits, Verdict::DoesNotFit]
+        );
+
+        // The estimate gets the same count for the wrong regions.
+        let guesses: Vec<bool> = regions.iter().map(guess_fit).collect();
+        assert_eq!(guesses, [false, true, true]);
+        assert_eq!(get_disagreements(&regions, &verdicts), [0, 2]);
+    }
+
+    #[test]
+    fn test_orientations() {
+        let square = [IVec2::ZERO, IVec2::X, IVec2::Y, IVec2::ONE];
+        assert_eq!(get_orientations(&square), [square.to_vec()]);
+
+        let skew = [IVec2::X, IVec2::new(2, 0), IVec2::Y, IVec2::ONE];
+        assert_eq!(get_orientations(&skew).len(), 4);
+
+        let corner = [IVec2::ZERO, IVec2::X, IVec2::Y];
+        let orientations = get_orientations(&corner);
+        assert_eq!(orientations.len(), 4);
+        assert!(orientations.contains(&vec![IVec2::ZERO, IVec2::new(-1, 1), IVec2::Y]));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
@@ -0,0 +1,380 @@
+/// Bit-level helpers for puzzles about toggling and choosing: a fixed-width [`BitSet`],
+/// allocation-free subset and combination iterators over `u64` masks, and linear algebra
+/// over GF(2) for "which switches produce this pattern" puzzles.
+use std::{
+    fmt::Display,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign},
+};
+
+/// A set of small integers below `64 * WORDS`, stored inline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BitSet<const WORDS: usize = 1> {
+    words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> Default for BitSet<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> BitSet<WORDS> {
+    /// The number of values the set can hold.
+    pub const CAPACITY: usize = 64 * WORDS;
+
+    pub const fn new() -> Self {
+        BitSet { words: [0; WORDS] }
+    }
+
+    /// Adds `value`, returning `true` if it was not in the set yet.
+    /// Panics if `value` is not below [`Self::CAPACITY`].
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, bit) = (value / 64, 1 << (value % 64));
+        let inserted = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        inserted
+    }
+
+    /// Removes `value`, returning `true` if it was in the set.
+    pub fn remove(&mut self, value: usize) -> bool {
+        let removed = self.contains(value);
+        if removed {
+            self.words[value / 64] &= !(1 << (value % 64));
+        }
+        removed
+    }
+
+    /// Adds `value` if it is missing and removes it otherwise.
+    pub fn toggle(&mut self, value: usize) {
+        self.words[value / 64] ^= 1 << (value % 64);
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.words
+            .get(value / 64)
+            .is_some_and(|word| word & (1 << (value % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .all(|(a, b)| a & !b == 0)
+    }
+
+    /// Iterates the values in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &word)| ones(word).map(move |bit| 64 * i + bit))
+    }
+}
+
+impl From<u64> for BitSet<1> {
+    fn from(bits: u64) -> Self {
+        BitSet { words: [bits] }
+    }
+}
+
+impl From<BitSet<1>> for u64 {
+    fn from(set: BitSet<1>) -> Self {
+        set.words[0]
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for BitSet<WORDS> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+impl<const WORDS: usize> Display for BitSet<WORDS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let values: Vec<String> = self.iter().map(|v| v.to_string()).collect();
+        write!(f, "{{{}}}", values.join(", "))
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($op:ident, $fn:ident, $assign_op:ident, $assign_fn:ident) => {
+        impl<const WORDS: usize> $assign_op for BitSet<WORDS> {
+            fn $assign_fn(&mut self, rhs: Self) {
+                for (a, b) in self.words.iter_mut().zip(rhs.words) {
+                    a.$assign_fn(b);
+                }
+            }
+        }
+
+        impl<const WORDS: usize> $op for BitSet<WORDS> {
+            type Output = Self;
+
+            fn $fn(mut self, rhs: Self) -> Self {
+                self.$assign_fn(rhs);
+                self
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+/* -------------------------------------------------------------------------- */
+
+/// Iterates the indices of the set bits of `mask` in ascending order.
+pub fn ones(mut mask: u64) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        (mask != 0).then(|| {
+            let bit = mask.trailing_zeros() as usize;
+            mask &= mask - 1;
+            bit
+        })
+    })
+}
+
+/// Iterates all subsets of `n` items as masks, from the empty set upwards.
+/// Panics if `n` is 64 or more.
+pub fn subsets(n: u32) -> impl Iterator<Item = u64> {
+    0..1u64 << n
+}
+
+/// Iterates all subsets of `mask`, including `mask` itself and the empty set,
+/// in descending order.
+pub fn submasks(mask: u64) -> impl Iterator<Item = u64> {
+    let mut next = Some(mask);
+    std::iter::from_fn(move || {
+        let current = next?;
+        next = (current != 0).then(|| (current - 1) & mask);
+        Some(current)
+    })
+}
+
+/// Iterates the subsets of `n` items with exactly `k` items as masks, in ascending order.
+pub fn combinations(n: u32, k: u32) -> impl Iterator<Item = u64> {
+    let limit = 1u128 << n;
+    let mut next = (k <= n).then_some((1u128 << k) - 1);
+
+    std::iter::from_fn(move || {
+        let current = next?;
+        // Gosper's hack: the next larger integer with the same number of bits.
+        next = (current != 0)
+            .then(|| {
+                let lowest = current & current.wrapping_neg();
+                let ripple = current + lowest;
+                (((ripple ^ current) >> 2) / lowest) | ripple
+            })
+            .filter(|&n| n < limit);
+        Some(current as u64)
+    })
+}
+
+/// Iterates all subsets of `n` items as masks in Gray code order, where each subset
+/// differs from the previous one in exactly one item: the trailing zeros of the step.
+pub fn gray_code(n: u32) -> impl Iterator<Item = u64> {
+    subsets(n).map(|i| i ^ (i >> 1))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The solutions of a linear system over GF(2): every solution is `particular` XOR
+/// some combination of the `null_space` vectors. Bit `i` is the value of variable `i`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Gf2Solution {
+    pub particular: u64,
+    pub null_space: Vec<u64>,
+}
+
+impl Gf2Solution {
+    /// Iterates all solutions, changing one null space vector at a time.
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        let mut current = self.particular;
+        subsets(self.null_space.len() as u32).map(move |step| {
+            if step > 0 {
+                current ^= self.null_space[step.trailing_zeros() as usize];
+            }
+            current
+        })
+    }
+
+    /// Returns the solution with the fewest variables set.
+    /// Runs in `2^k` steps for `k` free variables.
+    pub fn min_weight(&self) -> u64 {
+        self.iter()
+            .min_by_key(|solution| solution.count_ones())
+            .unwrap_or(self.particular)
+    }
+}
+
+/// Solves `x_0 * columns[0] ^ x_1 * columns[1] ^ ... = target` over GF(2), with Gaussian
+/// elimination. Each column is the mask of equations a variable toggles, such as the
+/// lights toggled by a button. Supports up to 64 variables and 64 equations.
+/// Returns `None` if the system has no solution.
+pub fn solve_gf2(columns: &[u64], target: u64) -> Option<Gf2Solution> {
+    assert!(columns.len() <= 64, "at most 64 variables are supported");
+
+    // One row per equation: the mask of variables which toggle it, and the wanted value.
+    let equations = columns.iter().fold(target, |acc, &c| acc | c);
+    let mut rows: Vec<(u64, bool)> = ones(equations)
+        .map(|e| {
+            let variables = columns
+                .iter()
+                .enumerate()
+                .filter(|&(_, c)| c & (1 << e) != 0)
+                .fold(0, |acc, (i, _)| acc | 1 << i);
+            (variables, target & (1 << e) != 0)
+        })
+        .collect();
+
+    // Reduce to row echelon form, remembering the pivot variable of each row.
+    let mut pivots = Vec::new();
+    for variable in 0..columns.len() {
+        let bit = 1 << variable;
+        let Some(found) = (pivots.len()..rows.len()).find(|&r| rows[r].0 & bit != 0) else {
+            continue;
+        };
+        rows.swap(pivots.len(), found);
+
+        let pivot = rows[pivots.len()];
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r != pivots.len() && row.0 & bit != 0 {
+                row.0 ^= pivot.0;
+                row.1 ^= pivot.1;
+            }
+        }
+        pivots.push(variable);
+    }
+
+    // A remaining row without variables must not require a toggle.
+    if rows[pivots.len()..].iter().any(|&(_, value)| value) {
+        return None;
+    }
+
+    let particular = pivots
+        .iter()
+        .zip(&rows)
+        .filter(|(_, (_, value))| *value)
+        .fold(0, |acc, (&variable, _)| acc | 1 << variable);
+
+    let pivot_mask = pivots.iter().fold(0u64, |acc, &v| acc | 1 << v);
+    let free = (0..columns.len()).filter(|&v| pivot_mask & (1 << v) == 0);
+    let null_space = free
+        .map(|f| {
+            pivots
+                .iter()
+                .zip(&rows)
+                .filter(|(_, (variables, _))| variables & (1 << f) != 0)
+                .fold(1 << f, |acc, (&variable, _)| acc | 1 << variable)
+        })
+        .collect();
+
+    Some(Gf2Solution {
+        particular,
+        null_space,
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modifies_bit_set() {
+        let mut set = BitSet::<2>::new();
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(100));
+        set.toggle(64);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![3, 64, 100]);
+        assert!(set.remove(64));
+        assert!(!set.contains(64));
+        assert!(!set.contains(1000));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.to_string(), "{3, 100}");
+    }
+
+    #[test]
+    fn combines_bit_sets() {
+        let a: BitSet = [1, 2, 3].into_iter().collect();
+        let b: BitSet = [3, 4].into_iter().collect();
+        assert_eq!(u64::from(a | b), 0b11110);
+        assert_eq!(u64::from(a & b), 0b1000);
+        assert_eq!(u64::from(a ^ b), 0b10110);
+        assert!((a & b).is_subset(&a));
+        assert!(!b.is_subset(&a));
+        assert_eq!(BitSet::from(0b101), [0, 2].into_iter().collect());
+    }
+
+    #[test]
+    fn iterates_subsets() {
+        assert_eq!(ones(0b1010_0001).collect::<Vec<_>>(), vec![0, 5, 7]);
+        assert_eq!(subsets(3).count(), 8);
+        assert_eq!(
+            submasks(0b101).collect::<Vec<_>>(),
+            vec![0b101, 0b100, 0b001, 0]
+        );
+    }
+
+    #[test]
+    fn iterates_combinations() {
+        assert_eq!(
+            combinations(4, 2).collect::<Vec<_>>(),
+            vec![0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]
+        );
+        assert_eq!(combinations(5, 0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(combinations(64, 64).collect::<Vec<_>>(), vec![u64::MAX]);
+        assert_eq!(combinations(2, 3).count(), 0);
+        assert_eq!(combinations(10, 3).count(), 120);
+    }
+
+    #[test]
+    fn iterates_gray_code() {
+        let codes: Vec<u64> = gray_code(3).collect();
+        assert_eq!(codes, vec![0, 1, 3, 2, 6, 7, 5, 4]);
+        for (i, pair) in codes.windows(2).enumerate() {
+            assert_eq!(pair[0] ^ pair[1], 1 << (i + 1).trailing_zeros());
+        }
+    }
+
+    #[test]
+    fn solves_gf2_systems() {
+        // Buttons toggling lights (3), (1,3), (2), (2,3), (0,2), (0,1) to reach `.##.`.
+        let buttons = [0b1000, 0b1010, 0b0100, 0b1100, 0b0101, 0b0011];
+        let solution = solve_gf2(&buttons, 0b0110).unwrap();
+
+        assert_eq!(solution.iter().count(), 4);
+        for x in solution.iter() {
+            let lights = ones(x).fold(0, |acc, i| acc ^ buttons[i]);
+            assert_eq!(lights, 0b0110);
+        }
+        assert_eq!(solution.min_weight().count_ones(), 2);
+    }
+
+    #[test]
+    fn detects_unsolvable_gf2_systems() {
+        assert_eq!(solve_gf2(&[0b011, 0b110], 0b001), None);
+        assert_eq!(solve_gf2(&[], 0b1), None);
+        assert_eq!(
+            solve_gf2(&[], 0),
+            Some(Gf2Solution {
+                particular: 0,
+                null_space: vec![]
+            })
+        );
+    }
+}
@@ -0,0 +1,192 @@
+/// Collections for hot loops: hash maps and sets with the fast, non-cryptographic `FxHash`
+/// in place of the DoS-resistant default, and a bitmap set for positions on a bounded grid.
+use std::collections::{HashMap, HashSet};
+
+use glam::IVec2;
+
+use crate::bits::ones;
+
+pub use rustc_hash::{FxBuildHasher, FxHasher};
+
+/// A `HashMap` using `FxHash`. Create it with `FastMap::default()`.
+pub type FastMap<K, V> = HashMap<K, V, FxBuildHasher>;
+
+/// A `HashSet` using `FxHash`. Create it with `FastSet::default()`.
+pub type FastSet<T> = HashSet<T, FxBuildHasher>;
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of positions within a `width` x `height` grid, stored as one bit per cell.
+/// Positions outside of the grid are never contained; inserting them panics.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GridSet {
+    width: usize,
+    height: usize,
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl GridSet {
+    pub fn new(width: usize, height: usize) -> Self {
+        GridSet {
+            width,
+            height,
+            words: vec![0; (width * height).div_ceil(64)],
+            len: 0,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn get_index(&self, pos: IVec2) -> Option<usize> {
+        let x = usize::try_from(pos.x).ok()?;
+        let y = usize::try_from(pos.y).ok()?;
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn get_pos(&self, index: usize) -> IVec2 {
+        IVec2::new((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    /// Adds `pos`, returning whether it was newly inserted. Panics if `pos` is outside
+    /// of the grid.
+    pub fn insert(&mut self, pos: IVec2) -> bool {
+        let Some(i) = self.get_index(pos) else {
+            panic!(
+                "position {pos} is outside of the {}x{} grid",
+                self.width, self.height
+            );
+        };
+        let (word, bit) = (&mut self.words[i / 64], 1 << (i % 64));
+        let inserted = *word & bit == 0;
+        *word |= bit;
+        self.len += usize::from(inserted);
+        inserted
+    }
+
+    /// Removes `pos`, returning whether it was present.
+    pub fn remove(&mut self, pos: IVec2) -> bool {
+        let Some(i) = self.get_index(pos) else {
+            return false;
+        };
+        let (word, bit) = (&mut self.words[i / 64], 1 << (i % 64));
+        let removed = *word & bit != 0;
+        *word &= !bit;
+        self.len -= usize::from(removed);
+        removed
+    }
+
+    pub fn contains(&self, pos: IVec2) -> bool {
+        self.get_index(pos)
+            .is_some_and(|i| self.words[i / 64] & (1 << (i % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+        self.len = 0;
+    }
+
+    /// Iterates the contained positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = IVec2> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(w, &word)| ones(word).map(move |bit| w * 64 + bit))
+            .map(|i| self.get_pos(i))
+    }
+}
+
+impl Extend<IVec2> for GridSet {
+    fn extend<I: IntoIterator<Item = IVec2>>(&mut self, iter: I) {
+        for pos in iter {
+            self.insert(pos);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use glam::IVec2;
+
+    use super::{FastMap, FastSet, GridSet};
+
+    #[test]
+    fn uses_fast_hasher() {
+        let mut map: FastMap<&str, u32> = FastMap::default();
+        *map.entry("a").or_default() += 2;
+        *map.entry("a").or_default() += 3;
+        assert_eq!(map.get("a"), Some(&5));
+
+        let set: FastSet<IVec2> = [IVec2::ZERO, IVec2::ONE, IVec2::ZERO].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn inserts_and_removes_positions() {
+        let mut set = GridSet::new(13, 7);
+        assert!(set.is_empty());
+
+        assert!(set.insert(IVec2::new(12, 6)));
+        assert!(set.insert(IVec2::new(0, 5)));
+        assert!(!set.insert(IVec2::new(12, 6)));
+        assert_eq!(set.len(), 2);
+
+        assert!(set.contains(IVec2::new(12, 6)));
+        assert!(!set.contains(IVec2::new(11, 6)));
+        assert!(!set.contains(IVec2::new(13, 0)));
+        assert!(!set.contains(IVec2::new(-1, 0)));
+
+        assert!(set.remove(IVec2::new(12, 6)));
+        assert!(!set.remove(IVec2::new(12, 6)));
+        assert!(!set.remove(IVec2::new(-1, 0)));
+        assert_eq!(set.len(), 1);
+
+        set.clear();
+        assert!(set.is_empty());
+        assert!(!set.contains(IVec2::new(0, 5)));
+    }
+
+    #[test]
+    fn iterates_in_row_major_order() {
+        let mut set = GridSet::new(10, 10);
+        let positions = [
+            IVec2::new(9, 9),
+            IVec2::new(3, 0),
+            IVec2::new(4, 6),
+            IVec2::new(0, 7),
+        ];
+        set.extend(positions);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [
+                IVec2::new(3, 0),
+                IVec2::new(4, 6),
+                IVec2::new(0, 7),
+                IVec2::new(9, 9)
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the 4x4 grid")]
+    fn rejects_positions_outside_of_the_grid() {
+        GridSet::new(4, 4).insert(IVec2::new(4, 0));
+    }
+}
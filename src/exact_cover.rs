@@ -0,0 +1,512 @@
+/// Exact cover with Knuth's Algorithm X on dancing links. Every primary column must be
+/// covered by exactly one chosen row, every secondary column by at most one, which
+/// models tilings where some cells may stay empty.
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+/// Limits on a search. Nodes count the partial solutions the search has visited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Budget {
+    pub nodes: Option<u64>,
+    pub time: Option<Duration>,
+}
+
+impl Budget {
+    pub const UNLIMITED: Budget = Budget {
+        nodes: None,
+        time: None,
+    };
+
+    pub fn nodes(nodes: u64) -> Self {
+        Budget {
+            nodes: Some(nodes),
+            time: None,
+        }
+    }
+
+    pub fn time(time: Duration) -> Self {
+        Budget {
+            nodes: None,
+            time: Some(time),
+        }
+    }
+}
+
+/// The search stopped before it was complete.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BudgetExceeded {
+    pub nodes: u64,
+    pub solutions: u64,
+}
+
+impl std::error::Error for BudgetExceeded {}
+
+impl Display for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "search budget exceeded after {} nodes and {} solutions",
+            self.nodes, self.solutions
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An exact cover problem. Columns `0..primary` are primary, the following `secondary`
+/// columns are secondary. Rows are referred to by the order they were added in.
+#[derive(Clone, Debug)]
+pub struct ExactCover {
+    primary: usize,
+    secondary: usize,
+    rows: Vec<Vec<usize>>,
+    budget: Budget,
+}
+
+impl ExactCover {
+    pub fn new(primary: usize, secondary: usize) -> Self {
+        ExactCover {
+            primary,
+            secondary,
+            rows: Vec::new(),
+            budget: Budget::UNLIMITED,
+        }
+    }
+
+    /// Adds a row covering `columns` and returns its id. Panics if a column is out of range.
+    pub fn add_row(&mut self, columns: impl IntoIterator<Item = usize>) -> usize {
+        let mut row: Vec<usize> = columns.into_iter().collect();
+        row.sort_unstable();
+        row.dedup();
+        if let Some(&column) = row.last() {
+            assert!(
+                column < self.primary + self.secondary,
+                "column {column} is out of range for {} columns",
+                self.primary + self.secondary
+            );
+        }
+        self.rows.push(row);
+        self.rows.len() - 1
+    }
+
+    pub fn set_budget(&mut self, budget: Budget) {
+        self.budget = budget;
+    }
+
+    /// Returns the columns of row `id`, sorted. Panics if there is no such row.
+    pub fn row(&self, id: usize) -> &[usize] {
+        &self.rows[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Iterates the solutions as sorted row ids. If the budget runs out, the last item
+    /// is the error.
+    pub fn solutions(&self) -> Solutions {
+        Solutions::new(self)
+    }
+
+    pub fn first_solution(&self) -> Result<Option<Vec<usize>>, BudgetExceeded> {
+        self.solutions().next().transpose()
+    }
+
+    pub fn count_solutions(&self) -> Result<u64, BudgetExceeded> {
+        let mut count = 0;
+        let mut solutions = self.solutions();
+        while let Some(solution) = solutions.advance() {
+            solution?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A node of the dancing links. Index 0 is the root, followed by one header per
+/// column; the headers of primary columns form the root's row.
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    left: usize,
+    right: usize,
+    up: usize,
+    down: usize,
+    column: usize,
+    /// The row id, unused for headers.
+    row: usize,
+}
+
+/// Where the search continues on the next call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Step {
+    /// Enter a new level, choosing a column to cover.
+    Enter,
+    /// Undo the row chosen at the current level and try the next one.
+    Retry,
+    Done,
+}
+
+/// The iterator returned by [`ExactCover::solutions`]. It runs Knuth's iterative
+/// Algorithm X, suspending at every solution.
+pub struct Solutions {
+    nodes: Vec<Node>,
+    sizes: Vec<usize>,
+    /// The row node chosen at every level.
+    choices: Vec<usize>,
+    step: Step,
+    budget: Budget,
+    started: Instant,
+    visited: u64,
+    found: u64,
+}
+
+impl Solutions {
+    fn new(problem: &ExactCover) -> Self {
+        let columns = problem.primary + problem.secondary;
+        let mut nodes: Vec<Node> = (0..=columns)
+            .map(|i| Node {
+                left: i,
+                right: i,
+                up: i,
+                down: i,
+                column: i,
+                row: usize::MAX,
+            })
+            .collect();
+
+        // Link the root and the primary headers into a ring; secondary headers stay
+        // alone, so they are never chosen.
+        for i in 0..=problem.primary {
+            nodes[i].right = (i + 1) % (problem.primary + 1);
+            nodes[(i + 1) % (problem.primary + 1)].left = i;
+        }
+
+        let mut sizes = vec![0; columns + 1];
+        for (id, row) in problem.rows.iter().enumerate() {
+            let first = nodes.len();
+            for &column in row {
+                let header = column + 1;
+                let node = nodes.len();
+                let up = nodes[header].up;
+                nodes.push(Node {
+                    left: node,
+                    right: node,
+                    up,
+                    down: header,
+                    column: header,
+                    row: id,
+                });
+                nodes[up].down = node;
+                nodes[header].up = node;
+                sizes[header] += 1;
+            }
+
+            let last = nodes.len();
+            for (i, node) in (first..).zip(&mut nodes[first..]) {
+                node.left = if i == first { last - 1 } else { i - 1 };
+                node.right = if i + 1 == last { first } else { i + 1 };
+            }
+        }
+
+        Solutions {
+            nodes,
+            sizes,
+            choices: Vec::new(),
+            step: Step::Enter,
+            budget: problem.budget,
+            started: Instant::now(),
+            visited: 0,
+            found: 0,
+        }
+    }
+
+    fn cover(&mut self, column: usize) {
+        let Node { left, right, .. } = self.nodes[column];
+        self.nodes[left].right = right;
+        self.nodes[right].left = left;
+
+        let mut i = self.nodes[column].down;
+        while i != column {
+            let mut j = self.nodes[i].right;
+            while j != i {
+                let Node { up, down, .. } = self.nodes[j];
+                self.nodes[up].down = down;
+                self.nodes[down].up = up;
+                self.sizes[self.nodes[j].column] -= 1;
+                j = self.nodes[j].right;
+            }
+            i = self.nodes[i].down;
+        }
+    }
+
+    fn uncover(&mut self, column: usize) {
+        let mut i = self.nodes[column].up;
+        while i != column {
+            let mut j = self.nodes[i].left;
+            while j != i {
+                let Node { up, down, .. } = self.nodes[j];
+                self.nodes[up].down = j;
+                self.nodes[down].up = j;
+                self.sizes[self.nodes[j].column] += 1;
+                j = self.nodes[j].left;
+            }
+            i = self.nodes[i].up;
+        }
+
+        let Node { left, right, .. } = self.nodes[column];
+        self.nodes[left].right = column;
+        self.nodes[right].left = column;
+    }
+
+    /// Covers the other columns of the row of `node`.
+    fn choose(&mut self, node: usize) {
+        let mut j = self.nodes[node].right;
+        while j != node {
+            self.cover(self.nodes[j].column);
+            j = self.nodes[j].right;
+        }
+    }
+
+    fn unchoose(&mut self, node: usize) {
+        let mut j = self.nodes[node].left;
+        while j != node {
+            self.uncover(self.nodes[j].column);
+            j = self.nodes[j].left;
+        }
+    }
+
+    /// Returns the primary column with the fewest rows left.
+    fn get_column(&self) -> usize {
+        let mut best = self.nodes[0].right;
+        let mut column = best;
+        while column != 0 {
+            if self.sizes[column] < self.sizes[best] {
+                best = column;
+            }
+            column = self.nodes[column].right;
+        }
+        best
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.budget.nodes.is_some_and(|nodes| self.visited > nodes)
+            || self
+                .budget
+                .time
+                .is_some_and(|time| self.visited % 1024 == 1 && self.started.elapsed() >= time)
+    }
+
+    fn get_solution(&self) -> Vec<usize> {
+        let mut solution: Vec<usize> = self.choices.iter().map(|&i| self.nodes[i].row).collect();
+        solution.sort_unstable();
+        solution
+    }
+
+    /// Runs the search up to the next solution.
+    fn advance(&mut self) -> Option<Result<Vec<usize>, BudgetExceeded>> {
+        loop {
+            match self.step {
+                Step::Done => return None,
+                Step::Enter => {
+                    if self.nodes[0].right == 0 {
+                        self.found += 1;
+                        self.step = Step::Retry;
+                        return Some(Ok(self.get_solution()));
+                    }
+
+                    self.visited += 1;
+                    if self.is_exhausted() {
+                        self.step = Step::Done;
+                        return Some(Err(BudgetExceeded {
+                            nodes: self.visited,
+                            solutions: self.found,
+                        }));
+                    }
+
+                    let column = self.get_column();
+                    self.cover(column);
+                    self.choices.push(column);
+                    self.next_row();
+                }
+                Step::Retry => {
+                    let Some(&node) = self.choices.last() else {
+                        self.step = Step::Done;
+                        continue;
+                    };
+                    self.unchoose(node);
+                    self.next_row();
+                }
+            }
+        }
+    }
+
+    /// Moves the choice of the current level to the next row of its column and covers
+    /// it, or backtracks to the previous level once the column is exhausted.
+    fn next_row(&mut self) {
+        let Some(node) = self.choices.pop() else {
+            return;
+        };
+        let column = self.nodes[node].column;
+        let next = self.nodes[node].down;
+        if next == column {
+            self.uncover(column);
+            self.step = if self.choices.is_empty() {
+                Step::Done
+            } else {
+                Step::Retry
+            };
+        } else {
+            self.choose(next);
+            self.choices.push(next);
+            self.step = Step::Enter;
+        }
+    }
+}
+
+impl Iterator for Solutions {
+    type Item = Result<Vec<usize>, BudgetExceeded>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.advance()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Budget, BudgetExceeded, ExactCover};
+
+    /// Places dominoes on a `width` x `height` board, with every cell a primary column.
+    fn get_dominoes(width: usize, height: usize) -> ExactCover {
+        let mut problem = ExactCover::new(width * height, 0);
+        for y in 0..height {
+            for x in 0..width {
+                let cell = y * width + x;
+                if x + 1 < width {
+                    problem.add_row([cell, cell + 1]);
+                }
+                if y + 1 < height {
+                    problem.add_row([cell, cell + width]);
+                }
+            }
+        }
+        problem
+    }
+
+    /// Places `n` queens, one per rank and file (primary), at most one per diagonal
+    /// (secondary).
+    fn get_queens(n: usize) -> ExactCover {
+        let mut problem = ExactCover::new(2 * n, 2 * (2 * n - 1));
+        for rank in 0..n {
+            for file in 0..n {
+                let diagonal = 2 * n + rank + file;
+                let anti_diagonal = 2 * n + (2 * n - 1) + (n - 1 + rank - file);
+                problem.add_row([rank, n + file, diagonal, anti_diagonal]);
+            }
+        }
+        problem
+    }
+
+    #[test]
+    fn solves_knuths_example() {
+        let mut problem = ExactCover::new(7, 0);
+        for row in [
+            &[2, 4, 5][..],
+            &[0, 3, 6],
+            &[1, 2, 5],
+            &[0, 3],
+            &[1, 6],
+            &[3, 4, 6],
+        ] {
+            problem.add_row(row.iter().copied());
+        }
+        assert_eq!(problem.len(), 6);
+        assert_eq!(problem.first_solution(), Ok(Some(vec![0, 3, 4])));
+        assert_eq!(problem.count_solutions(), Ok(1));
+    }
+
+    #[test]
+    fn counts_domino_tilings() {
+        // The 2 x n tilings are counted by the Fibonacci numbers.
+        assert_eq!(get_dominoes(10, 2).count_solutions(), Ok(89));
+        assert_eq!(get_dominoes(4, 4).count_solutions(), Ok(36));
+        assert_eq!(get_dominoes(6, 6).count_solutions(), Ok(6728));
+        assert_eq!(get_dominoes(3, 3).first_solution(), Ok(None));
+    }
+
+    #[test]
+    fn iterates_all_solutions() {
+        let problem = get_dominoes(4, 2);
+        let solutions: Vec<Vec<usize>> = problem.solutions().map(Result::unwrap).collect();
+        assert_eq!(solutions.len(), 5);
+
+        for solution in &solutions {
+            let mut cells: Vec<usize> = solution
+                .iter()
+                .flat_map(|&row| problem.row(row).iter().copied())
+                .collect();
+            cells.sort_unstable();
+            assert_eq!(cells, (0..8).collect::<Vec<_>>());
+        }
+
+        let mut distinct = solutions.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), solutions.len());
+    }
+
+    #[test]
+    fn leaves_secondary_columns_uncovered() {
+        assert_eq!(get_queens(6).count_solutions(), Ok(4));
+        assert_eq!(get_queens(8).count_solutions(), Ok(92));
+        assert_eq!(get_queens(3).first_solution(), Ok(None));
+
+        // Only secondary columns: the empty selection is the one solution.
+        let mut problem = ExactCover::new(0, 2);
+        problem.add_row([0, 1]);
+        assert_eq!(problem.first_solution(), Ok(Some(vec![])));
+    }
+
+    #[test]
+    fn stops_when_the_budget_runs_out() {
+        let mut problem = get_queens(8);
+        problem.set_budget(Budget::nodes(100));
+        let Err(BudgetExceeded { nodes, .. }) = problem.count_solutions() else {
+            panic!("expected the budget to run out");
+        };
+        assert_eq!(nodes, 101);
+
+        let mut solutions = problem.solutions();
+        assert!(solutions.any(|solution| solution.is_err()));
+        assert!(solutions.next().is_none());
+
+        problem.set_budget(Budget::time(Duration::ZERO));
+        assert_eq!(
+            problem.count_solutions(),
+            Err(BudgetExceeded {
+                nodes: 1,
+                solutions: 0
+            })
+        );
+
+        problem.set_budget(Budget::UNLIMITED);
+        assert_eq!(problem.count_solutions(), Ok(92));
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is out of range for 3 columns")]
+    fn rejects_unknown_columns() {
+        ExactCover::new(2, 1).add_row([0, 3]);
+    }
+}
@@ -0,0 +1,381 @@
+/// Axis-aligned geometry on the tile grid: rectangles, rectilinear polygons given by
+/// their corner tiles, coordinate compression and 2D prefix sums. A polygon covers the
+/// tiles on its boundary and inside it, as if it was drawn through the tile centers.
+use std::fmt::Display;
+
+use glam::IVec2;
+
+use crate::grid::{Grid, NEIGHBORS_4};
+
+/// An axis-aligned rectangle of tiles, including both corners.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: IVec2,
+    pub max: IVec2,
+}
+
+impl Rect {
+    /// Creates the rectangle spanned by two opposite corners in any order.
+    pub fn from_corners(a: IVec2, b: IVec2) -> Self {
+        Rect {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    pub fn width(&self) -> u64 {
+        u64::from(self.max.x.abs_diff(self.min.x)) + 1
+    }
+
+    pub fn height(&self) -> u64 {
+        u64::from(self.max.y.abs_diff(self.min.y)) + 1
+    }
+
+    /// Returns the number of tiles covered.
+    pub fn area(&self) -> u64 {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, pos: IVec2) -> bool {
+        pos.cmpge(self.min).all() && pos.cmple(self.max).all()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Maps tile coordinates to a coarser grid of cells. Every given coordinate gets a
+/// cell of its own, and the tiles between two given coordinates share one cell, so
+/// anything bounded by the given coordinates looks the same for all tiles of a cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Compression {
+    /// The first tile of every cell per axis, with an outer cell on each side.
+    xs: Vec<i32>,
+    ys: Vec<i32>,
+}
+
+impl Compression {
+    pub fn new(points: &[IVec2]) -> Self {
+        let get_starts = |coords: Vec<i32>| {
+            let mut starts: Vec<i32> = coords
+                .iter()
+                .flat_map(|&c| [c, c + 1])
+                .chain([i32::MIN])
+                .collect();
+            starts.sort_unstable();
+            starts.dedup();
+            starts
+        };
+
+        Compression {
+            xs: get_starts(points.iter().map(|p| p.x).collect()),
+            ys: get_starts(points.iter().map(|p| p.y).collect()),
+        }
+    }
+
+    /// Returns the number of cells per axis.
+    pub fn size(&self) -> IVec2 {
+        IVec2::new(self.xs.len() as i32, self.ys.len() as i32)
+    }
+
+    /// Returns the cell containing the tile at `pos`.
+    pub fn get_cell(&self, pos: IVec2) -> IVec2 {
+        let find = |starts: &[i32], c: i32| starts.partition_point(|&s| s <= c) as i32 - 1;
+        IVec2::new(find(&self.xs, pos.x), find(&self.ys, pos.y))
+    }
+
+    /// Returns the first tile of a cell.
+    pub fn get_tile(&self, cell: IVec2) -> IVec2 {
+        IVec2::new(self.xs[cell.x as usize], self.ys[cell.y as usize])
+    }
+
+    /// Returns the number of tiles in a cell, or `None` for the unbounded outer cells.
+    pub fn get_tile_count(&self, cell: IVec2) -> Option<u64> {
+        let length = |starts: &[i32], i: usize| {
+            let end = starts.get(i + 1)?;
+            (i > 0).then(|| u64::from(end.abs_diff(starts[i])))
+        };
+        Some(length(&self.xs, cell.x as usize)? * length(&self.ys, cell.y as usize)?)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Sums over rectangular areas of a grid in constant time.
+#[derive(Clone, Debug)]
+pub struct PrefixSums {
+    width: usize,
+    /// `sums[y * width + x]` is the sum of all cells before `(x, y)` on both axes,
+    /// with one extra row and column.
+    sums: Vec<u64>,
+}
+
+impl PrefixSums {
+    pub fn new(grid: &Grid<u64>) -> Self {
+        let width = grid.width() + 1;
+        let mut sums = vec![0u64; width * (grid.height() + 1)];
+
+        for (pos, &value) in grid.iter() {
+            let (x, y) = (pos.x as usize, pos.y as usize);
+            sums[(y + 1) * width + x + 1] =
+                value + sums[y * width + x + 1] + sums[(y + 1) * width + x] - sums[y * width + x];
+        }
+
+        PrefixSums { width, sums }
+    }
+
+    /// Returns the sum of the cells in `rect`.
+    pub fn sum(&self, rect: Rect) -> u64 {
+        let at = |x: i32, y: i32| self.sums[y as usize * self.width + x as usize];
+        let (min, max) = (rect.min, rect.max + 1);
+        at(max.x, max.y) + at(min.x, min.y) - at(min.x, max.y) - at(max.x, min.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A closed polygon with axis-aligned edges, given by its corner tiles in order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RectilinearPolygon {
+    vertices: Vec<IVec2>,
+}
+
+/// An error which can be returned when creating a [`RectilinearPolygon`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum PolygonError {
+    TooFewVertices,
+    /// The edge from the vertex at `index` to the next one is not axis-aligned.
+    DiagonalEdge {
+        index: usize,
+    },
+}
+
+impl std::error::Error for PolygonError {}
+
+impl Display for PolygonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolygonError::TooFewVertices => write!(f, "a polygon needs at least four vertices"),
+            PolygonError::DiagonalEdge { index } => {
+                write!(f, "the edge starting at vertex {index} is not axis-aligned")
+            }
+        }
+    }
+}
+
+impl RectilinearPolygon {
+    pub fn new(vertices: Vec<IVec2>) -> Result<Self, PolygonError> {
+        if vertices.len() < 4 {
+            return Err(PolygonError::TooFewVertices);
+        }
+        let polygon = RectilinearPolygon { vertices };
+        let diagonal = polygon.edges().position(|(a, b)| a.x != b.x && a.y != b.y);
+        match diagonal {
+            Some(index) => Err(PolygonError::DiagonalEdge { index }),
+            None => Ok(polygon),
+        }
+    }
+
+    pub fn vertices(&self) -> &[IVec2] {
+        &self.vertices
+    }
+
+    /// Iterates the edges as pairs of consecutive vertices, including the closing one.
+    pub fn edges(&self) -> impl Iterator<Item = (IVec2, IVec2)> + '_ {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a, b))
+    }
+
+    /// Returns `true` if the tile at `pos` is on the boundary or inside.
+    pub fn contains(&self, pos: IVec2) -> bool {
+        let mut inside = false;
+        for (a, b) in self.edges() {
+            if Rect::from_corners(a, b).contains(pos) {
+                return true;
+            }
+            // Cast a ray to the right, counting vertical edges with a half-open span
+            // so that a ray through a vertex is counted once.
+            let (low, high) = (a.y.min(b.y), a.y.max(b.y));
+            if a.x == b.x && a.x > pos.x && (low..high).contains(&pos.y) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    /// Returns which cells of `compression` are covered by the polygon. The vertices
+    /// need to be part of the compression.
+    pub fn get_covered_cells(&self, compression: &Compression) -> Grid<bool> {
+        let size = compression.size();
+        let mut boundary = Grid::new(size.x as usize, size.y as usize, false);
+        for (a, b) in self.edges() {
+            let edge = Rect::from_corners(compression.get_cell(a), compression.get_cell(b));
+            for x in edge.min.x..=edge.max.x {
+                for y in edge.min.y..=edge.max.y {
+                    boundary[IVec2::new(x, y)] = true;
+                }
+            }
+        }
+
+        // Everything reachable from the outer corner without crossing the boundary is outside.
+        let mut covered = Grid::new(size.x as usize, size.y as usize, true);
+        let mut stack = vec![IVec2::ZERO];
+        covered[IVec2::ZERO] = false;
+        while let Some(cell) = stack.pop() {
+            for next in NEIGHBORS_4.map(|offset| cell + offset) {
+                if covered.get(next) == Some(&true) && !boundary[next] {
+                    covered[next] = false;
+                    stack.push(next);
+                }
+            }
+        }
+
+        covered
+    }
+
+    /// Returns the largest rectangle with two of `corners` as opposite corners, which
+    /// only covers tiles of the polygon. Runs in `O(n²)` for `n` corners and vertices.
+    pub fn largest_rectangle(&self, corners: &[IVec2]) -> Option<Rect> {
+        let points = [self.vertices.as_slice(), corners].concat();
+        let compression = Compression::new(&points);
+
+        // A rectangle fits if none of its cells is uncovered.
+        let uncovered = self
+            .get_covered_cells(&compression)
+            .map(|&inside| u64::from(!inside));
+        let sums = PrefixSums::new(&uncovered);
+
+        let cells: Vec<IVec2> = corners.iter().map(|&c| compression.get_cell(c)).collect();
+        let mut best: Option<Rect> = None;
+        for i in 0..corners.len() {
+            for j in i + 1..corners.len() {
+                let rect = Rect::from_corners(corners[i], corners[j]);
+                if best.is_some_and(|best| best.area() >= rect.area()) {
+                    continue;
+                }
+                if sums.sum(Rect::from_corners(cells[i], cells[j])) == 0 {
+                    best = Some(rect);
+                }
+            }
+        }
+
+        best
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use glam::IVec2;
+
+    use super::{Compression, PolygonError, PrefixSums, Rect, RectilinearPolygon};
+    use crate::grid::Grid;
+
+    fn get_polygon() -> RectilinearPolygon {
+        let vertices = [
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ];
+        RectilinearPolygon::new(vertices.map(|(x, y)| IVec2::new(x, y)).to_vec()).unwrap()
+    }
+
+    #[test]
+    fn measures_rectangles() {
+        let rect = Rect::from_corners(IVec2::new(11, 1), IVec2::new(2, 5));
+        assert_eq!(rect.min, IVec2::new(2, 1));
+        assert_eq!((rect.width(), rect.height(), rect.area()), (10, 5, 50));
+        assert!(rect.contains(IVec2::new(11, 5)));
+        assert!(!rect.contains(IVec2::new(12, 5)));
+    }
+
+    #[test]
+    fn validates_polygons() {
+        let square = [(0, 0), (2, 0), (2, 2), (0, 2)].map(|(x, y)| IVec2::new(x, y));
+        assert!(RectilinearPolygon::new(square.to_vec()).is_ok());
+        assert_eq!(
+            RectilinearPolygon::new(square[..3].to_vec()),
+            Err(PolygonError::TooFewVertices)
+        );
+
+        let mut skewed = square;
+        skewed[3] = IVec2::new(1, 2);
+        assert_eq!(
+            RectilinearPolygon::new(skewed.to_vec()),
+            Err(PolygonError::DiagonalEdge { index: 3 })
+        );
+    }
+
+    #[test]
+    fn contains_tiles() {
+        let polygon = get_polygon();
+        let covered = "\
+            ..............\n\
+            .......XXXXX..\n\
+            .......XXXXX..\n\
+            ..XXXXXXXXXX..\n\
+            ..XXXXXXXXXX..\n\
+            ..XXXXXXXXXX..\n\
+            .........XXX..\n\
+            .........XXX..\n\
+            ..............\n";
+        let expected = Grid::parse(covered).unwrap();
+        for (pos, &c) in expected.iter() {
+            assert_eq!(polygon.contains(pos), c == 'X', "{pos}");
+        }
+    }
+
+    #[test]
+    fn compresses_coordinates() {
+        let compression = Compression::new(&[IVec2::new(2, 10), IVec2::new(7, 10)]);
+        assert_eq!(compression.size(), IVec2::new(5, 3));
+        assert_eq!(compression.get_cell(IVec2::new(5, 10)), IVec2::new(2, 1));
+        assert_eq!(compression.get_tile(IVec2::new(2, 1)), IVec2::new(3, 10));
+        assert_eq!(compression.get_tile_count(IVec2::new(2, 1)), Some(4));
+        assert_eq!(compression.get_tile_count(IVec2::ZERO), None);
+        assert_eq!(compression.get_tile_count(IVec2::new(4, 1)), None);
+    }
+
+    #[test]
+    fn covers_compressed_cells() {
+        let polygon = get_polygon();
+        let compression = Compression::new(polygon.vertices());
+        let covered = polygon.get_covered_cells(&compression);
+        for (cell, &inside) in covered.iter() {
+            assert_eq!(inside, polygon.contains(compression.get_tile(cell)));
+        }
+    }
+
+    #[test]
+    fn sums_rectangles() {
+        let grid = Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+        let sums = PrefixSums::new(&grid);
+        assert_eq!(
+            sums.sum(Rect::from_corners(IVec2::ZERO, IVec2::new(2, 1))),
+            21
+        );
+        assert_eq!(
+            sums.sum(Rect::from_corners(IVec2::new(1, 0), IVec2::new(2, 1))),
+            16
+        );
+        assert_eq!(
+            sums.sum(Rect::from_corners(IVec2::new(0, 1), IVec2::new(0, 1))),
+            4
+        );
+    }
+
+    #[test]
+    fn finds_largest_rectangle() {
+        let polygon = get_polygon();
+        let rect = polygon.largest_rectangle(polygon.vertices()).unwrap();
+        assert_eq!(rect, Rect::from_corners(IVec2::new(9, 5), IVec2::new(2, 3)));
+        assert_eq!(rect.area(), 24);
+    }
+}
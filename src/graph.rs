@@ -0,0 +1,409 @@
+/// Graph utilities over dense `usize` node ids: a union-find, string interning,
+/// adjacency-list graphs with DAG path counting, and BFS/Dijkstra on top of `pathfinding`.
+use std::{collections::VecDeque, hash::Hash};
+
+use pathfinding::prelude::{bfs, dijkstra};
+
+use crate::collections::FastMap;
+
+/// A disjoint-set union (union-find) with path compression and union by size.
+#[derive(Clone, Debug)]
+pub struct Dsu {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl Dsu {
+    /// Creates `len` singleton sets.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            sets: len,
+        }
+    }
+
+    /// Returns the representative of the set containing `x`.
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            // path halving: point every other node to its grandparent.
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merges the sets containing `a` and `b`. Returns `false` if they already were the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.sets -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Returns the size of the set containing `x`.
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+
+    /// Returns the sizes of all sets, largest first.
+    pub fn set_sizes(&mut self) -> Vec<usize> {
+        let roots: Vec<usize> = (0..self.parent.len())
+            .filter(|x| self.find(*x) == *x)
+            .collect();
+        let mut sizes: Vec<usize> = roots.into_iter().map(|root| self.size[root]).collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Assigns dense ids to keys in insertion order, e.g. to turn node names into indices.
+#[derive(Clone, Debug, Default)]
+pub struct Interner<K> {
+    ids: FastMap<K, usize>,
+    keys: Vec<K>,
+}
+
+impl<K: Hash + Eq + Clone> Interner<K> {
+    pub fn new() -> Self {
+        Self {
+            ids: FastMap::default(),
+            keys: Vec::new(),
+        }
+    }
+
+    /// Returns the id of `key`, assigning the next free id if it's new.
+    pub fn intern(&mut self, key: K) -> usize {
+        if let Some(&id) = self.ids.get(&key) {
+            return id;
+        }
+        let id = self.keys.len();
+        self.keys.push(key.clone());
+        self.ids.insert(key, id);
+        id
+    }
+
+    pub fn get(&self, key: &K) -> Option<usize> {
+        self.ids.get(key).copied()
+    }
+
+    /// Returns the key of `id`. Panics if the id was not assigned.
+    pub fn key(&self, id: usize) -> &K {
+        &self.keys[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A directed, unweighted graph stored as adjacency lists.
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    adj: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    /// Creates a graph with `len` nodes and no edges.
+    pub fn new(len: usize) -> Self {
+        Self {
+            adj: vec![Vec::new(); len],
+        }
+    }
+
+    /// Adds the edge `from -> to`, growing the graph if either node is new.
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        let len = from.max(to) + 1;
+        if self.adj.len() < len {
+            self.adj.resize(len, Vec::new());
+        }
+        self.adj[from].push(to);
+    }
+
+    pub fn len(&self) -> usize {
+        self.adj.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adj.is_empty()
+    }
+
+    pub fn neighbors(&self, node: usize) -> &[usize] {
+        &self.adj[node]
+    }
+
+    /// Returns the successors of `node` in the shape expected by `pathfinding`.
+    pub fn successors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adj[node].iter().copied()
+    }
+
+    /// Returns which nodes can be reached from `start`, including itself.
+    pub fn reachable_from(&self, start: usize) -> Vec<bool> {
+        let mut reachable = vec![false; self.len()];
+        let mut stack = vec![start];
+        reachable[start] = true;
+
+        while let Some(u) = stack.pop() {
+            for &v in &self.adj[u] {
+                if !reachable[v] {
+                    reachable[v] = true;
+                    stack.push(v);
+                }
+            }
+        }
+
+        reachable
+    }
+
+    /// Returns the nodes reachable from `start` in topological order (Kahn's algorithm),
+    /// or `None` if they contain a cycle.
+    pub fn topological_order(&self, start: usize) -> Option<Vec<usize>> {
+        let reachable = self.reachable_from(start);
+
+        let mut indegree = vec![0_usize; self.len()];
+        for u in (0..self.len()).filter(|u| reachable[*u]) {
+            for &v in &self.adj[u] {
+                indegree[v] += 1;
+            }
+        }
+
+        let mut queue: VecDeque<usize> = (0..self.len())
+            .filter(|u| reachable[*u] && indegree[*u] == 0)
+            .collect();
+
+        let mut order = Vec::new();
+        while let Some(u) = queue.pop_front() {
+            order.push(u);
+            for &v in &self.adj[u] {
+                indegree[v] -= 1;
+                if indegree[v] == 0 {
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        let count = reachable.iter().filter(|r| **r).count();
+        (order.len() == count).then_some(order)
+    }
+
+    /// Counts the paths from `start` to `goal` in a DAG. Returns `None` if a cycle is reachable.
+    pub fn count_paths(&self, start: usize, goal: usize) -> Option<u64> {
+        self.count_paths_through(start, goal, &[])
+    }
+
+    /// Counts the paths from `start` to `goal` in a DAG that visit all `required` nodes.
+    /// Returns `None` if a cycle is reachable. Supports up to 16 required nodes.
+    pub fn count_paths_through(
+        &self,
+        start: usize,
+        goal: usize,
+        required: &[usize],
+    ) -> Option<u64> {
+        assert!(required.len() <= 16, "too many required nodes");
+
+        let order = self.topological_order(start)?;
+        let masks = 1 << required.len();
+        let full = masks - 1;
+
+        let bit = |node: usize| -> usize {
+            required
+                .iter()
+                .enumerate()
+                .filter(|(_, r)| **r == node)
+                .fold(0, |acc, (i, _)| acc | 1 << i)
+        };
+
+        // paths[u * masks + mask]: paths from u to the goal, given `mask` was collected before u.
+        let mut paths = vec![0_u64; self.len() * masks];
+
+        for &u in order.iter().rev() {
+            let node_bit = bit(u);
+            for mask in 0..masks {
+                let collected = mask | node_bit;
+                paths[u * masks + mask] = if u == goal {
+                    u64::from(collected == full)
+                } else {
+                    self.adj[u]
+                        .iter()
+                        .map(|v| paths[v * masks + collected])
+                        .fold(0, u64::saturating_add)
+                };
+            }
+        }
+
+        Some(paths[start * masks])
+    }
+
+    /// Returns a shortest path from `start` to `goal`, including both ends.
+    pub fn shortest_path(&self, start: usize, goal: usize) -> Option<Vec<usize>> {
+        bfs(&start, |u| self.successors(*u), |u| *u == goal)
+    }
+
+    /// Returns the number of edges on a shortest path from `start` to every node.
+    pub fn distances_from(&self, start: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        let mut queue = VecDeque::from([start]);
+        distances[start] = Some(0);
+
+        while let Some(u) = queue.pop_front() {
+            let next = distances[u].map(|d| d + 1);
+            for &v in &self.adj[u] {
+                if distances[v].is_none() {
+                    distances[v] = next;
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        distances
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A directed graph with non-negative edge costs, stored as adjacency lists.
+#[derive(Clone, Debug, Default)]
+pub struct WeightedGraph {
+    adj: Vec<Vec<(usize, u64)>>,
+}
+
+impl WeightedGraph {
+    pub fn new(len: usize) -> Self {
+        Self {
+            adj: vec![Vec::new(); len],
+        }
+    }
+
+    /// Adds the edge `from -> to`, growing the graph if either node is new.
+    pub fn add_edge(&mut self, from: usize, to: usize, cost: u64) {
+        let len = from.max(to) + 1;
+        if self.adj.len() < len {
+            self.adj.resize(len, Vec::new());
+        }
+        self.adj[from].push((to, cost));
+    }
+
+    pub fn len(&self) -> usize {
+        self.adj.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adj.is_empty()
+    }
+
+    /// Returns the successors of `node` in the shape expected by `pathfinding`.
+    pub fn successors(&self, node: usize) -> impl Iterator<Item = (usize, u64)> + '_ {
+        self.adj[node].iter().copied()
+    }
+
+    /// Returns a cheapest path from `start` to `goal` with its total cost.
+    pub fn shortest_path(&self, start: usize, goal: usize) -> Option<(Vec<usize>, u64)> {
+        dijkstra(&start, |u| self.successors(*u), |u| *u == goal)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Dsu, Graph, Interner, WeightedGraph};
+
+    #[test]
+    fn unions_sets() {
+        let mut dsu = Dsu::new(5);
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(3, 4));
+        assert!(dsu.union(1, 4));
+        assert!(!dsu.union(0, 3));
+
+        assert!(dsu.same(0, 4));
+        assert!(!dsu.same(0, 2));
+        assert_eq!(dsu.size_of(3), 4);
+        assert_eq!(dsu.set_count(), 2);
+        assert_eq!(dsu.set_sizes(), [4, 1]);
+    }
+
+    #[test]
+    fn interns_keys() {
+        let mut interner = Interner::new();
+        assert_eq!(interner.intern("you"), 0);
+        assert_eq!(interner.intern("out"), 1);
+        assert_eq!(interner.intern("you"), 0);
+        assert_eq!(interner.get(&"out"), Some(1));
+        assert_eq!(interner.get(&"svr"), None);
+        assert_eq!(*interner.key(1), "out");
+        assert_eq!(interner.len(), 2);
+    }
+
+    /// 0 -> 1 -> 3, 0 -> 2 -> 3, 1 -> 2
+    fn get_mock_dag() -> Graph {
+        let mut graph = Graph::new(0);
+        for (u, v) in [(0, 1), (0, 2), (1, 3), (2, 3), (1, 2)] {
+            graph.add_edge(u, v);
+        }
+        graph
+    }
+
+    #[test]
+    fn orders_topologically() {
+        let graph = get_mock_dag();
+        assert_eq!(graph.topological_order(0), Some(vec![0, 1, 2, 3]));
+        assert_eq!(graph.topological_order(2), Some(vec![2, 3]));
+
+        let mut cyclic = graph.clone();
+        cyclic.add_edge(3, 1);
+        assert_eq!(cyclic.topological_order(0), None);
+        assert_eq!(cyclic.count_paths(0, 3), None);
+    }
+
+    #[test]
+    fn counts_paths() {
+        let graph = get_mock_dag();
+        assert_eq!(graph.count_paths(0, 3), Some(3));
+        assert_eq!(graph.count_paths(3, 0), Some(0));
+        assert_eq!(graph.count_paths_through(0, 3, &[2]), Some(2));
+        assert_eq!(graph.count_paths_through(0, 3, &[1, 2]), Some(1));
+    }
+
+    #[test]
+    fn finds_shortest_paths() {
+        let graph = get_mock_dag();
+        assert_eq!(graph.shortest_path(0, 3).map(|p| p.len()), Some(3));
+        assert_eq!(graph.shortest_path(3, 0), None);
+        assert_eq!(graph.distances_from(1), [None, Some(0), Some(1), Some(1)]);
+
+        let mut weighted = WeightedGraph::new(3);
+        weighted.add_edge(0, 1, 5);
+        weighted.add_edge(0, 2, 1);
+        weighted.add_edge(2, 1, 1);
+        assert_eq!(weighted.shortest_path(0, 1), Some((vec![0, 2, 1], 2)));
+    }
+}
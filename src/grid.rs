@@ -0,0 +1,418 @@
+/// A dense, row-major 2D grid addressed by `IVec2` positions, where `x` is the column
+/// and `y` is the row, growing to the right and down.
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use glam::IVec2;
+
+/// The orthogonal neighbor offsets, clockwise starting up.
+pub const NEIGHBORS_4: [IVec2; 4] = [
+    IVec2::new(0, -1),
+    IVec2::new(1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 0),
+];
+
+/// The orthogonal and diagonal neighbor offsets, clockwise starting up.
+pub const NEIGHBORS_8: [IVec2; 8] = [
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(1, 0),
+    IVec2::new(1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, 0),
+    IVec2::new(-1, -1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// A line's length differs from the first line. The line number is 1-based.
+    Ragged {
+        line: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl std::error::Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Ragged {
+                line,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "line {line} has {actual} cells, expected {expected} like the first line"
+            ),
+        }
+    }
+}
+
+impl Grid<char> {
+    /// Parses puzzle text into a grid of characters.
+    pub fn parse(input: &str) -> Result<Self, GridError> {
+        Self::parse_with(input, |c| c)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses puzzle text, converting each character with `f`.
+    /// Empty lines at the end of the input are ignored.
+    pub fn parse_with(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for (i, line) in input.trim_end_matches(['\n', '\r']).lines().enumerate() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let actual = cells.len() - before;
+
+            match width {
+                None => width = Some(actual),
+                Some(expected) if expected != actual => {
+                    return Err(GridError::Ragged {
+                        line: i + 1,
+                        expected,
+                        actual,
+                    });
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    /// Creates a grid from row-major cells. Returns `None` if the length doesn't match.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Option<Self> {
+        (cells.len() == width * height).then_some(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the size as a vector, i.e. the position just past the bottom-right corner.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    pub fn size(&self) -> IVec2 {
+        IVec2::new(self.width as i32, self.height as i32)
+    }
+
+    fn get_index(&self, pos: IVec2) -> Option<usize> {
+        let x = usize::try_from(pos.x).ok()?;
+        let y = usize::try_from(pos.y).ok()?;
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn get_pos(&self, index: usize) -> IVec2 {
+        IVec2::new((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    pub fn contains(&self, pos: IVec2) -> bool {
+        self.get_index(pos).is_some()
+    }
+
+    pub fn get(&self, pos: IVec2) -> Option<&T> {
+        self.get_index(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: IVec2) -> Option<&mut T> {
+        self.get_index(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Iterates all positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = IVec2> + use<T> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            IVec2::new((i % width) as i32, (i / width) as i32)
+        })
+    }
+
+    /// Iterates all cells with their position in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.get_pos(i), cell))
+    }
+
+    /// Iterates the orthogonal neighbors of `pos` that are inside the grid.
+    pub fn neighbors4(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        NEIGHBORS_4
+            .iter()
+            .map(move |offset| pos + offset)
+            .filter(|n| self.contains(*n))
+    }
+
+    /// Iterates the orthogonal and diagonal neighbors of `pos` that are inside the grid.
+    pub fn neighbors8(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        NEIGHBORS_8
+            .iter()
+            .map(move |offset| pos + offset)
+            .filter(|n| self.contains(*n))
+    }
+
+    /// Returns row `y`. Panics if it's out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics for a chunk size of 0, an empty grid has no rows either way.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterates the cells of column `x` from top to bottom. Panics if it's out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Returns the position of the first cell equal to `value`, in row-major order.
+    pub fn find(&self, value: &T) -> Option<IVec2>
+    where
+        T: PartialEq,
+    {
+        self.cells
+            .iter()
+            .position(|cell| cell == value)
+            .map(|i| self.get_pos(i))
+    }
+
+    /// Iterates the positions of all cells equal to `value`, in row-major order.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = IVec2> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Builds a grid of size `width` x `height` where the cell at `(x, y)` is `self[f(x, y)]`.
+    fn remap(&self, width: usize, height: usize, f: impl Fn(usize, usize) -> usize) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self.cells[f(x, y)].clone())
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.remap(self.height, self.width, |x, y| x * width + y)
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let (width, height) = (self.width, self.height);
+        self.remap(height, width, |x, y| (height - 1 - x) * width + y)
+    }
+
+    /// Rotates the grid by 90 degrees counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.remap(self.height, width, |x, y| x * width + (width - 1 - y))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid filled with `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Index<IVec2> for Grid<T> {
+    type Output = T;
+
+    /// Panics if `pos` is outside of the grid, use [`Grid::get`] for a checked access.
+    fn index(&self, pos: IVec2) -> &Self::Output {
+        match self.get_index(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "position {pos} is outside of the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<IVec2> for Grid<T> {
+    fn index_mut(&mut self, pos: IVec2) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        match self.get_index(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("position {pos} is outside of the {width}x{height} grid"),
+        }
+    }
+}
+
+/// Renders one row per line, without a trailing newline.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use glam::IVec2;
+
+    use super::{Grid, GridError};
+
+    fn get_mock_grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n").unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = get_mock_grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(IVec2::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(IVec2::new(3, 0)), None);
+        assert_eq!(grid.get(IVec2::new(0, -1)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+    }
+
+    #[test]
+    fn rejects_ragged_grids() {
+        assert_eq!(
+            Grid::parse("abc\nde\n"),
+            Err(GridError::Ragged {
+                line: 2,
+                expected: 3,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn parses_with_conversion() {
+        let grid = Grid::parse_with("12\n34", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(grid[IVec2::new(1, 1)], 4);
+    }
+
+    #[test]
+    fn iterates_neighbors_in_bounds() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.neighbors4(IVec2::ZERO).count(), 2);
+        assert_eq!(grid.neighbors8(IVec2::ZERO).count(), 3);
+        assert_eq!(grid.neighbors8(IVec2::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn views_rows_and_columns() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.columns().count(), 3);
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = Grid::parse("a.a\n.a.").unwrap();
+        assert_eq!(grid.find(&'.'), Some(IVec2::new(1, 0)));
+        assert_eq!(grid.find(&'x'), None);
+        assert_eq!(
+            grid.find_all(&'a').collect::<Vec<_>>(),
+            [IVec2::new(0, 0), IVec2::new(2, 0), IVec2::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+
+    #[test]
+    fn maps_cells() {
+        let grid = get_mock_grid().map(|c| c.is_ascii_lowercase());
+        assert!(grid.iter().all(|(_, cell)| *cell));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_out_of_bounds_index() {
+        let _ = get_mock_grid()[IVec2::new(3, 0)];
+    }
+}
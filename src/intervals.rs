@@ -0,0 +1,251 @@
+/// A set of integers stored as sorted, disjoint inclusive intervals, for puzzles about
+/// ranges that are too large to enumerate.
+use std::{fmt::Display, ops::RangeInclusive};
+
+/// Adjacent and overlapping intervals are merged on insert, so `1..=3` and `4..=6`
+/// are stored as `1..=6`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    /// Sorted by start, disjoint and not adjacent.
+    intervals: Vec<(u64, u64)>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a set from already sorted and disjoint intervals, merging adjacent ones.
+    fn from_sorted(sorted: impl IntoIterator<Item = (u64, u64)>) -> Self {
+        let mut intervals: Vec<(u64, u64)> = Vec::new();
+        for (start, end) in sorted {
+            match intervals.last_mut() {
+                Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+                _ => intervals.push((start, end)),
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    /// Adds all values of `range`, merging it with overlapping and adjacent intervals.
+    /// Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<u64>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // The intervals in `from..to` overlap or touch the new one.
+        let from = self
+            .intervals
+            .partition_point(|&(_, e)| e.saturating_add(1) < start);
+        let to = self
+            .intervals
+            .partition_point(|&(s, _)| s <= end.saturating_add(1));
+
+        if from < to {
+            start = start.min(self.intervals[from].0);
+            end = end.max(self.intervals[to - 1].1);
+        }
+        self.intervals.splice(from..to, [(start, end)]);
+    }
+
+    /// Returns `true` if `value` is in one of the intervals, in O(log n).
+    pub fn contains(&self, value: u64) -> bool {
+        let i = self.intervals.partition_point(|&(_, end)| end < value);
+        self.intervals
+            .get(i)
+            .is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// Returns the values in either set.
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut all = [self.intervals.as_slice(), other.intervals.as_slice()].concat();
+        all.sort_unstable();
+        Self::from_sorted(all)
+    }
+
+    /// Returns the values in both sets.
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(&(a_start, a_end)), Some(&(b_start, b_end))) =
+            (self.intervals.get(i), other.intervals.get(j))
+        {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                intervals.push((start, end));
+            }
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    /// Returns the values in this set but not in `other`.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+
+        for &(start, end) in &self.intervals {
+            let mut start = Some(start);
+
+            // Skip intervals of `other` which end before this one starts.
+            while other
+                .intervals
+                .get(j)
+                .is_some_and(|&(_, e)| e < start.unwrap())
+            {
+                j += 1;
+            }
+
+            let mut k = j;
+            while let (Some(s), Some(&(cut_start, cut_end))) = (start, other.intervals.get(k)) {
+                if cut_start > end {
+                    break;
+                }
+                if cut_start > s {
+                    intervals.push((s, cut_start - 1));
+                }
+                start = cut_end.checked_add(1).filter(|&s| s <= end);
+                k += 1;
+            }
+
+            if let Some(s) = start {
+                intervals.push((s, end));
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    /// Returns the number of values covered by the set.
+    /// Saturates for a set covering all of `u64`.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().fold(0u64, |len, &(start, end)| {
+            len.saturating_add((end - start).saturating_add(1))
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns the number of disjoint intervals.
+    pub fn interval_count(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Iterates the disjoint intervals in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<u64>> + '_ {
+        self.intervals.iter().map(|&(start, end)| start..=end)
+    }
+}
+
+impl FromIterator<RangeInclusive<u64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<u64>>>(iter: I) -> Self {
+        let mut all: Vec<(u64, u64)> = iter
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(RangeInclusive::into_inner)
+            .collect();
+        all.sort_unstable();
+        Self::from_sorted(all)
+    }
+}
+
+impl Extend<RangeInclusive<u64>> for IntervalSet {
+    fn extend<I: IntoIterator<Item = RangeInclusive<u64>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl Display for IntervalSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let intervals: Vec<String> = self
+            .intervals
+            .iter()
+            .map(|(start, end)| format!("{start}-{end}"))
+            .collect();
+        write!(f, "{{{}}}", intervals.join(", "))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::ops::RangeInclusive;
+
+    use super::IntervalSet;
+
+    fn set(ranges: &[(u64, u64)]) -> IntervalSet {
+        ranges.iter().map(|&(start, end)| start..=end).collect()
+    }
+
+    #[test]
+    fn merges_on_insert() {
+        let mut intervals = IntervalSet::new();
+        intervals.insert(10..=14);
+        intervals.insert(3..=5);
+        intervals.insert(16..=20);
+        assert_eq!(intervals.interval_count(), 3);
+
+        intervals.insert(12..=18);
+        assert_eq!(intervals, set(&[(3, 5), (10, 20)]));
+
+        intervals.insert(6..=9);
+        assert_eq!(intervals, set(&[(3, 20)]));
+
+        intervals.insert(RangeInclusive::new(30, 29));
+        assert_eq!(intervals.to_string(), "{3-20}");
+    }
+
+    #[test]
+    fn collects_unsorted_ranges() {
+        let intervals = set(&[(16, 20), (3, 5), (12, 18), (10, 14)]);
+        assert_eq!(intervals.iter().collect::<Vec<_>>(), vec![3..=5, 10..=20]);
+        assert_eq!(intervals.len(), 14);
+    }
+
+    #[test]
+    fn checks_membership() {
+        let intervals = set(&[(3, 5), (10, 20)]);
+        assert!(!intervals.contains(1));
+        assert!(intervals.contains(5));
+        assert!(!intervals.contains(8));
+        assert!(intervals.contains(10));
+        assert!(!intervals.contains(21));
+        assert!(!IntervalSet::new().contains(0));
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 24), (28, 28), (40, 50)]);
+
+        assert_eq!(a.union(&b), set(&[(0, 30), (40, 50)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 24), (28, 28)]));
+        assert_eq!(a.difference(&b), set(&[(0, 4), (25, 27), (29, 30)]));
+        assert_eq!(b.difference(&a), set(&[(11, 19), (40, 50)]));
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn handles_bounds() {
+        let all = set(&[(0, u64::MAX)]);
+        assert_eq!(all.len(), u64::MAX);
+        assert_eq!(
+            all.difference(&set(&[(0, 0), (u64::MAX, u64::MAX)])),
+            set(&[(1, u64::MAX - 1)])
+        );
+        assert_eq!(all.union(&set(&[(5, 6)])), all);
+    }
+}
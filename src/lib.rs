@@ -1,3 +1,14 @@
+pub mod bits;
+pub mod collections;
+pub mod exact_cover;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod intervals;
+pub mod linear;
+pub mod memo;
+pub mod parse;
+pub mod spatial;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
@@ -0,0 +1,386 @@
+/// A system of equations `rows * x = rhs` over non-negative integer unknowns, such as
+/// how often to press each button to reach a set of counters.
+///
+/// Elimination runs over the rationals, but keeps every row integral by cross-multiplying,
+/// so there is no rounding. The remaining free variables are enumerated within their
+/// bounds with branch and bound.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinearSystem {
+    rows: Vec<Vec<i64>>,
+    rhs: Vec<i64>,
+    variables: usize,
+}
+
+/// A row of the reduced system: `coefficient * x[variable] + sum(free * x[free]) = rhs`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct PivotRow {
+    variable: usize,
+    coefficient: i128,
+    /// The coefficients of the free variables, in the order of [`Reduced::free`].
+    free: Vec<i128>,
+    rhs: i128,
+}
+
+/// A system in reduced row echelon form, where each pivot variable is determined by
+/// the values of the free variables.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reduced {
+    variables: usize,
+    pivots: Vec<PivotRow>,
+    free: Vec<usize>,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+fn lcm(a: i128, b: i128) -> i128 {
+    a / gcd(a, b) * b
+}
+
+impl LinearSystem {
+    /// Creates a system where `rows[i][j]` is the coefficient of variable `j` in
+    /// equation `i`. Panics if the rows differ in length or do not match `rhs`.
+    pub fn new(rows: Vec<Vec<i64>>, rhs: Vec<i64>) -> Self {
+        assert_eq!(
+            rows.len(),
+            rhs.len(),
+            "every equation needs a right-hand side"
+        );
+        let variables = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == variables),
+            "all equations need the same number of coefficients"
+        );
+        LinearSystem {
+            rows,
+            rhs,
+            variables,
+        }
+    }
+
+    /// Creates a system where `columns[j][i]` is the coefficient of variable `j` in
+    /// equation `i`, such as the counters increased by a button.
+    pub fn from_columns(columns: &[Vec<i64>], rhs: Vec<i64>) -> Self {
+        let rows = (0..rhs.len())
+            .map(|i| columns.iter().map(|column| column[i]).collect())
+            .collect();
+        let mut system = Self::new(rows, rhs);
+        system.variables = columns.len();
+        system
+    }
+
+    pub fn variables(&self) -> usize {
+        self.variables
+    }
+
+    /// Derives an upper bound for every variable from equations with only non-negative
+    /// coefficients: a variable cannot exceed `rhs / coefficient` in any of them.
+    /// Returns `None` if a variable is not bounded that way.
+    pub fn get_upper_bounds(&self) -> Option<Vec<i64>> {
+        (0..self.variables)
+            .map(|j| {
+                self.rows
+                    .iter()
+                    .zip(&self.rhs)
+                    .filter(|&(row, &rhs)| row[j] > 0 && rhs >= 0 && row.iter().all(|&c| c >= 0))
+                    .map(|(row, &rhs)| rhs / row[j])
+                    .min()
+            })
+            .collect()
+    }
+
+    /// Reduces the system with Gaussian elimination.
+    /// Returns `None` if the equations contradict each other.
+    pub fn reduce(&self) -> Option<Reduced> {
+        let mut rows: Vec<Vec<i128>> = self
+            .rows
+            .iter()
+            .zip(&self.rhs)
+            .map(|(row, &rhs)| {
+                let mut row: Vec<i128> = row.iter().map(|&c| i128::from(c)).collect();
+                row.push(i128::from(rhs));
+                row
+            })
+            .collect();
+
+        let mut pivot_variables = Vec::new();
+        for variable in 0..self.variables {
+            let rank = pivot_variables.len();
+            let Some(found) = (rank..rows.len()).find(|&r| rows[r][variable] != 0) else {
+                continue;
+            };
+            rows.swap(rank, found);
+
+            let pivot = rows[rank].clone();
+            for (r, row) in rows.iter_mut().enumerate() {
+                let factor = row[variable];
+                if r == rank || factor == 0 {
+                    continue;
+                }
+                // Cross-multiplying instead of dividing keeps the rows integral.
+                for (value, &p) in row.iter_mut().zip(&pivot) {
+                    *value = *value * pivot[variable] - p * factor;
+                }
+                let divisor = row.iter().fold(0, |acc, &v| gcd(acc, v));
+                if divisor > 1 {
+                    row.iter_mut().for_each(|v| *v /= divisor);
+                }
+            }
+            pivot_variables.push(variable);
+        }
+
+        // A row without variables left must also have nothing on the right-hand side.
+        if rows[pivot_variables.len()..]
+            .iter()
+            .any(|row| row[self.variables] != 0)
+        {
+            return None;
+        }
+
+        let free: Vec<usize> = (0..self.variables)
+            .filter(|v| !pivot_variables.contains(v))
+            .collect();
+
+        let pivots = pivot_variables
+            .iter()
+            .zip(rows)
+            .map(|(&variable, row)| {
+                let sign = row[variable].signum();
+                PivotRow {
+                    variable,
+                    coefficient: row[variable] * sign,
+                    free: free.iter().map(|&f| row[f] * sign).collect(),
+                    rhs: row[self.variables] * sign,
+                }
+            })
+            .collect();
+
+        Some(Reduced {
+            variables: self.variables,
+            pivots,
+            free,
+        })
+    }
+
+    /// Finds the non-negative integer solution with `x[j] <= upper_bounds[j]` which
+    /// minimizes `sum(costs[j] * x[j])`. Ties are broken arbitrarily.
+    /// Returns `None` if there is no such solution.
+    pub fn minimize(&self, costs: &[i64], upper_bounds: &[i64]) -> Option<Vec<i64>> {
+        self.reduce()?.minimize(costs, upper_bounds)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Reduced {
+    /// Returns the variables which can be chosen freely, in ascending order.
+    pub fn free_variables(&self) -> &[usize] {
+        &self.free
+    }
+
+    /// Returns the number of independent equations.
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+
+    /// Returns the value of the pivot variable of `row`, if it is an integer.
+    fn get_pivot_value(row: &PivotRow, free_values: &[i64]) -> Option<i64> {
+        let sum: i128 = row
+            .free
+            .iter()
+            .zip(free_values)
+            .map(|(&c, &x)| c * i128::from(x))
+            .sum();
+        let numerator = row.rhs - sum;
+        (numerator % row.coefficient == 0)
+            .then(|| i64::try_from(numerator / row.coefficient).ok())
+            .flatten()
+    }
+
+    /// Completes a solution from the values of the free variables, in the order of
+    /// [`Self::free_variables`]. Returns `None` if the solution is not integral.
+    pub fn solve(&self, free_values: &[i64]) -> Option<Vec<i64>> {
+        assert_eq!(free_values.len(), self.free.len());
+        let mut x = vec![0; self.variables];
+        for (&f, &value) in self.free.iter().zip(free_values) {
+            x[f] = value;
+        }
+        for row in &self.pivots {
+            x[row.variable] = Self::get_pivot_value(row, free_values)?;
+        }
+        Some(x)
+    }
+
+    /// See [`LinearSystem::minimize`].
+    pub fn minimize(&self, costs: &[i64], upper_bounds: &[i64]) -> Option<Vec<i64>> {
+        assert_eq!(costs.len(), self.variables);
+        assert_eq!(upper_bounds.len(), self.variables);
+
+        // Substituting the pivot rows expresses the cost in the free variables alone,
+        // scaled by `scale` to stay integral: `offset + sum(reduced[f] * x[f])`.
+        let scale = self
+            .pivots
+            .iter()
+            .fold(1, |acc, row| lcm(acc, row.coefficient));
+        let mut offset = 0;
+        let mut reduced: Vec<i128> = self
+            .free
+            .iter()
+            .map(|&f| scale * i128::from(costs[f]))
+            .collect();
+        for row in &self.pivots {
+            let weight = i128::from(costs[row.variable]) * (scale / row.coefficient);
+            offset += weight * row.rhs;
+            for (r, &c) in reduced.iter_mut().zip(&row.free) {
+                *r -= weight * c;
+            }
+        }
+
+        // A pivot row can be checked as soon as its last free variable is assigned.
+        let mut checks: Vec<Vec<&PivotRow>> = vec![Vec::new(); self.free.len() + 1];
+        for row in &self.pivots {
+            let ready = row.free.iter().rposition(|&c| c != 0).map_or(0, |i| i + 1);
+            checks[ready].push(row);
+        }
+
+        // The lowest cost the unassigned free variables can still add.
+        let mut remaining = vec![0; self.free.len() + 1];
+        for i in (0..self.free.len()).rev() {
+            let bound = i128::from(upper_bounds[self.free[i]]);
+            remaining[i] = remaining[i + 1] + (reduced[i] * bound).min(0);
+        }
+
+        let mut search = Search {
+            reduced: &reduced,
+            checks: &checks,
+            remaining: &remaining,
+            upper_bounds,
+            free: &self.free,
+            values: vec![0; self.free.len()],
+            best: None,
+        };
+        if search.is_feasible(0) {
+            search.run(0, offset);
+        }
+
+        self.solve(&search.best?.1)
+    }
+}
+
+/// The state of the branch and bound over the free variables.
+struct Search<'a> {
+    reduced: &'a [i128],
+    checks: &'a [Vec<&'a PivotRow>],
+    remaining: &'a [i128],
+    upper_bounds: &'a [i64],
+    free: &'a [usize],
+    values: Vec<i64>,
+    best: Option<(i128, Vec<i64>)>,
+}
+
+impl Search<'_> {
+    /// Checks the pivot rows which are fully determined once `assigned` free variables
+    /// have a value.
+    fn is_feasible(&self, assigned: usize) -> bool {
+        self.checks[assigned].iter().all(|row| {
+            Reduced::get_pivot_value(row, &self.values)
+                .is_some_and(|x| (0..=self.upper_bounds[row.variable]).contains(&x))
+        })
+    }
+
+    fn run(&mut self, index: usize, cost: i128) {
+        if self
+            .best
+            .as_ref()
+            .is_some_and(|(best, _)| cost + self.remaining[index] >= *best)
+        {
+            return;
+        }
+
+        if index == self.free.len() {
+            self.best = Some((cost, self.values.clone()));
+            return;
+        }
+
+        let bound = self.upper_bounds[self.free[index]];
+        let step = self.reduced[index];
+        for i in 0..=bound {
+            // Try the cheapest values first to find a good bound early.
+            let value = if step >= 0 { i } else { bound - i };
+            self.values[index] = value;
+            if self.is_feasible(index + 1) {
+                self.run(index + 1, cost + step * i128::from(value));
+            }
+        }
+        self.values[index] = 0;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::LinearSystem;
+
+    #[test]
+    fn reduces_to_free_variables() {
+        // x + y + z = 6, y - z = 1
+        let system = LinearSystem::new(vec![vec![1, 1, 1], vec![0, 1, -1]], vec![6, 1]);
+        let reduced = system.reduce().unwrap();
+        assert_eq!(reduced.rank(), 2);
+        assert_eq!(reduced.free_variables(), &[2]);
+        assert_eq!(reduced.solve(&[2]), Some(vec![1, 3, 2]));
+    }
+
+    #[test]
+    fn detects_contradictions() {
+        let system = LinearSystem::new(vec![vec![1, 1], vec![2, 2]], vec![1, 3]);
+        assert_eq!(system.reduce(), None);
+    }
+
+    #[test]
+    fn rejects_fractional_solutions() {
+        // 2x = 3
+        let system = LinearSystem::new(vec![vec![2]], vec![3]);
+        assert_eq!(system.reduce().unwrap().solve(&[]), None);
+        assert_eq!(system.minimize(&[1], &[10]), None);
+    }
+
+    #[test]
+    fn derives_upper_bounds() {
+        let system = LinearSystem::from_columns(&[vec![1, 0], vec![2, 1]], vec![7, 3]);
+        assert_eq!(system.get_upper_bounds(), Some(vec![7, 3]));
+
+        let unbounded = LinearSystem::new(vec![vec![1, -1]], vec![0]);
+        assert_eq!(unbounded.get_upper_bounds(), None);
+    }
+
+    #[test]
+    fn minimizes_button_presses() {
+        // Buttons (3), (1,3), (2), (2,3), (0,2), (0,1) reaching counters {3,5,4,7}.
+        let buttons = [[3].as_slice(), &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let columns: Vec<Vec<i64>> = buttons
+            .iter()
+            .map(|b| (0..4).map(|i| i64::from(b.contains(&i))).collect())
+            .collect();
+        let system = LinearSystem::from_columns(&columns, vec![3, 5, 4, 7]);
+
+        let bounds = system.get_upper_bounds().unwrap();
+        let presses = system.minimize(&[1; 6], &bounds).unwrap();
+        assert_eq!(presses.iter().sum::<i64>(), 10);
+        for (i, &target) in [3, 5, 4, 7].iter().enumerate() {
+            let total: i64 = (0..6).map(|j| columns[j][i] * presses[j]).sum();
+            assert_eq!(total, target);
+        }
+    }
+
+    #[test]
+    fn minimizes_weighted_costs() {
+        // x + y = 4 where y is cheaper, but y <= 3.
+        let system = LinearSystem::new(vec![vec![1, 1]], vec![4]);
+        assert_eq!(system.minimize(&[5, 1], &[4, 3]), Some(vec![1, 3]));
+        assert_eq!(system.minimize(&[1, 5], &[4, 3]), Some(vec![4, 0]));
+        assert_eq!(system.minimize(&[1, 1], &[1, 1]), None);
+    }
+}
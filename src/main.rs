@@ -1,13 +1,15 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
-use args::{AppArguments, parse};
-
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
+#[cfg(feature = "encrypted-inputs")]
+use advent_of_code::template::commands::rekey;
 #[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
+use advent_of_code::template::commands::{
+    all, download, leaderboard, read, scaffold, solve, status, time, watch,
+};
+use args::{AppArguments, parse};
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{Day, Input, commands::leaderboard::Source};
+    use pico_args::Arguments;
     use std::process;
 
     pub enum AppArguments {
@@ -21,12 +23,18 @@ mod args {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
+            example_answers: Vec<String>,
+            dry_run: bool,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            part: Option<u8>,
+            input: Input,
+            watch: bool,
         },
         All {
             release: bool,
@@ -35,13 +43,27 @@ mod args {
             all: bool,
             day: Option<Day>,
             store: bool,
+            scale: bool,
+            part: Option<u8>,
+        },
+        Status {
+            markdown: bool,
+        },
+        Leaderboard {
+            source: Option<Source>,
+            me: Option<String>,
+            markdown: bool,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
+        #[cfg(feature = "encrypted-inputs")]
+        Rekey,
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        let mut args = Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
@@ -50,11 +72,15 @@ mod args {
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let scale = args.contains("--scale");
+                let part = parse_part(&mut args)?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    scale,
+                    part,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -67,15 +93,87 @@ mod args {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
+                example_answers: args
+                    .opt_value_from_str::<_, String>("--example-answers")?
+                    .map(|answers| answers.split(',').map(str::to_string).collect())
+                    .unwrap_or_default(),
+                dry_run: args.contains("--dry-run"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            Some("solve") => {
+                let day = args.free_from_str()?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let part = parse_part(&mut args)?;
+                let watch = args.contains("--watch");
+
+                // the input flags are passed on to the solution binary, parse them the same way.
+                let mut rest: Vec<String> =
+                    std::mem::replace(&mut args, Arguments::from_vec(vec![]))
+                        .finish()
+                        .into_iter()
+                        .map(|arg| arg.to_string_lossy().into_owned())
+                        .collect();
+                let input = Input::take_from_args(&mut rest)?;
+                args = Arguments::from_vec(rest.into_iter().map(Into::into).collect());
+
+                // the watched solution runs without a terminal and is re-run on every change.
+                if watch {
+                    let conflict = if input == Input::Stdin {
+                        Some("`--input -`, stdin is not forwarded to the re-runs")
+                    } else if submit.is_some() {
+                        Some("`--submit`, every change would submit an answer")
+                    } else if dhat {
+                        Some("`--dhat`, the re-runs are not profiled")
+                    } else {
+                        None
+                    };
+                    if let Some(conflict) = conflict {
+                        eprintln!("`--watch` can't be combined with {conflict}.");
+                        process::exit(1);
+                    }
+                }
+
+                AppArguments::Solve {
+                    day,
+                    release,
+                    dhat,
+                    submit,
+                    part,
+                    input,
+                    watch,
+                }
+            }
+            Some("status") => AppArguments::Status {
+                markdown: args.contains("--markdown"),
             },
+            Some("leaderboard") => {
+                let import: Option<String> = args.opt_value_from_str("--import")?;
+                let download: Option<String> = args.opt_value_from_str("--download")?;
+
+                let source = match (import, download) {
+                    (Some(_), Some(_)) => {
+                        eprintln!("`--import` and `--download` are exclusive.");
+                        process::exit(1);
+                    }
+                    (Some(path), None) => Some(Source::Import(path)),
+                    (None, Some(id)) => Some(Source::Download(id)),
+                    (None, None) => None,
+                };
+
+                AppArguments::Leaderboard {
+                    source,
+                    me: args.opt_value_from_str("--me")?,
+                    markdown: args.contains("--markdown"),
+                }
+            }
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
+            #[cfg(feature = "encrypted-inputs")]
+            Some("rekey") => AppArguments::Rekey,
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -93,6 +191,16 @@ mod args {
 
         Ok(app_args)
     }
+
+    fn parse_part(args: &mut Arguments) -> Result<Option<u8>, Box<dyn std::error::Error>> {
+        let part: Option<u8> = args.opt_value_from_str("--part")?;
+
+        if part.is_some_and(|part| part != 1 && part != 2) {
+            return Err("expecting `--part` to be 1 or 2".into());
+        }
+
+        Ok(part)
+    }
 }
 
 fn main() {
@@ -103,16 +211,37 @@ fn main() {
         }
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                scale,
+                part,
+            } => time::handle(day, all, store, scale, part),
+            AppArguments::Status { markdown } => status::handle(markdown),
+            AppArguments::Leaderboard {
+                source,
+                me,
+                markdown,
+            } => leaderboard::handle(source, me.as_deref(), markdown),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                template,
+                example_answers,
+                dry_run,
             } => {
-                scaffold::handle(day, overwrite);
-                if download {
+                scaffold::handle(
+                    day,
+                    overwrite,
+                    template.as_deref(),
+                    &example_answers,
+                    dry_run,
+                );
+                if download && !dry_run {
                     download::handle(day);
                 }
             }
@@ -121,24 +250,20 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+                part,
+                input,
+                watch,
+            } => {
+                if watch {
+                    watch::handle(day, release, part, &input);
+                } else {
+                    solve::handle(day, release, dhat, submit, part, &input);
+                }
             }
+            #[cfg(feature = "today")]
+            AppArguments::Today { wait } => today::handle(wait),
+            #[cfg(feature = "encrypted-inputs")]
+            AppArguments::Rekey => rekey::handle(),
         },
     };
 }
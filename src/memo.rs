@@ -0,0 +1,183 @@
+/// Memoization for recursive searches. A [`Memo`] caches results by key and counts cache
+/// hits and misses; the counts are reported by the runner when a part is run with `--time`.
+use std::{fmt::Display, hash::Hash, sync::Mutex};
+
+use crate::collections::FastMap;
+
+/// The statistics of all caches dropped since the last [`take_stats`], merged by name.
+static STATS: Mutex<Vec<MemoStats>> = Mutex::new(Vec::new());
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoStats {
+    pub name: &'static str,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl MemoStats {
+    /// Returns the share of lookups answered from the cache.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} hits, {} misses ({:.1}% hit rate)",
+            self.name,
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+/// Returns and resets the statistics of the caches dropped so far.
+pub fn take_stats() -> Vec<MemoStats> {
+    STATS
+        .lock()
+        .map(|mut stats| std::mem::take(&mut *stats))
+        .unwrap_or_default()
+}
+
+fn record_stats(new: MemoStats) {
+    let Ok(mut stats) = STATS.lock() else {
+        return;
+    };
+    match stats.iter_mut().find(|s| s.name == new.name) {
+        Some(existing) => {
+            existing.hits += new.hits;
+            existing.misses += new.misses;
+        }
+        None => stats.push(new),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A cache of function results by key. Its statistics are recorded under its name when
+/// it is dropped.
+///
+/// Recursive functions take the cache as parameter and wrap their body in [`Memo::call`],
+/// e.g. `fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64` calls
+/// `memo.call(n, |memo| fibonacci(memo, n - 1) + fibonacci(memo, n - 2))` for `n >= 2`.
+#[derive(Debug)]
+pub struct Memo<K, V> {
+    cache: FastMap<K, V>,
+    stats: MemoStats,
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new(name: &'static str) -> Self {
+        Memo {
+            cache: FastMap::default(),
+            stats: MemoStats {
+                name,
+                hits: 0,
+                misses: 0,
+            },
+        }
+    }
+
+    /// Returns the cached result for `key`, or computes it with `f` and caches it.
+    /// `f` receives the cache again, for recursive calls.
+    pub fn call(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let value = f(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+}
+
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        record_stats(self.stats);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Memo, MemoStats};
+
+    fn count_paths(memo: &mut Memo<(u32, u32), u64>, x: u32, y: u32) -> u64 {
+        memo.call((x, y), |memo| {
+            if x == 0 || y == 0 {
+                1
+            } else {
+                count_paths(memo, x - 1, y) + count_paths(memo, x, y - 1)
+            }
+        })
+    }
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.call(n, |memo| {
+            if n < 2 {
+                n
+            } else {
+                fibonacci(memo, n - 1) + fibonacci(memo, n - 2)
+            }
+        })
+    }
+
+    #[test]
+    fn computes_fibonacci() {
+        let mut memo = Memo::new("fibonacci");
+        assert_eq!(fibonacci(&mut memo, 90), 2_880_067_194_370_816_120);
+        assert_eq!(memo.len(), 91);
+    }
+
+    #[test]
+    fn caches_recursive_calls() {
+        let mut memo = Memo::new("paths");
+        assert_eq!(count_paths(&mut memo, 16, 16), 601_080_390);
+        assert_eq!(memo.len(), 17 * 17 - 1);
+        assert_eq!(memo.get(&(1, 1)), Some(&2));
+
+        let stats = memo.stats();
+        assert_eq!(stats.misses, memo.len() as u64);
+        assert_eq!(stats.hits, 2 * 16 * 16 - memo.len() as u64 + 1);
+    }
+
+    #[test]
+    fn formats_stats() {
+        let stats = MemoStats {
+            name: "paths",
+            hits: 3,
+            misses: 1,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "paths: 3 hits, 1 misses (75.0% hit rate)"
+        );
+    }
+}
@@ -0,0 +1,440 @@
+/// Helpers for the common shapes of puzzle input: numbers scattered over a line,
+/// blank-line separated sections, comma and range lists and bracketed token groups.
+/// Failures are reported as a [`ParseError`] pointing at the offending line and column.
+use std::fmt::Display;
+
+use atoi::{FromRadix10Checked, FromRadix10SignedChecked};
+
+/// An error which can be returned when parsing puzzle input.
+/// Lines and columns are 1-based, columns are counted in bytes.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A number was expected but not found.
+    ExpectedNumber,
+    /// A number does not fit into the target type.
+    Overflow,
+    /// A specific character was expected but not found.
+    Expected(char),
+    /// A character which is not allowed at this position.
+    Unexpected(char),
+    /// A bracketed group is missing its closing delimiter.
+    Unclosed(char),
+}
+
+impl std::error::Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match self.kind {
+            ErrorKind::ExpectedNumber => write!(f, "expected a number"),
+            ErrorKind::Overflow => write!(f, "number is out of range"),
+            ErrorKind::Expected(c) => write!(f, "expected '{c}'"),
+            ErrorKind::Unexpected(c) => write!(f, "unexpected '{c}'"),
+            ErrorKind::Unclosed(c) => write!(f, "missing closing '{c}'"),
+        }
+    }
+}
+
+impl ParseError {
+    /// Creates an error at the byte `offset` of `input`.
+    fn at(input: &str, offset: usize, kind: ErrorKind) -> Self {
+        let (line, column) = locate(input, offset);
+        ParseError { line, column, kind }
+    }
+
+    /// Moves an error found in a part of the input to its position in the whole input,
+    /// where the part starts at `line` and `column`.
+    fn within(mut self, line: usize, column: usize) -> Self {
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+        self.line += line - 1;
+        self
+    }
+}
+
+/// Returns the 1-based line and column of the byte `offset` in `input`.
+fn locate(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (before.matches('\n').count() + 1, offset - line_start + 1)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses every line with `f`, reporting errors at their line in `input`.
+pub fn lines<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.within(i + 1, 1)))
+        .collect()
+}
+
+/// Splits the input into sections separated by one or more blank lines.
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut sections = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if content.trim().is_empty() {
+            if let Some(s) = start.take() {
+                sections.push(&input[s..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + content.len();
+        }
+        offset += line.len();
+    }
+
+    if let Some(s) = start {
+        sections.push(&input[s..end]);
+    }
+    sections
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Extracts all unsigned integers, ignoring anything between them.
+pub fn unsigned<T: FromRadix10Checked>(input: &str) -> Result<Vec<T>, ParseError> {
+    let bytes = input.as_bytes();
+    let mut numbers = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i].is_ascii_digit() {
+            let (n, used) = T::from_radix_10_checked(&bytes[i..]);
+            numbers.push(n.ok_or_else(|| ParseError::at(input, i, ErrorKind::Overflow))?);
+            i += used;
+        } else {
+            i += 1;
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Extracts all signed integers, ignoring anything between them. A `-` or `+` directly
+/// in front of the digits counts as sign, unless it follows another digit, so that
+/// `3-5` yields `3` and `5`.
+pub fn signed<T: FromRadix10SignedChecked>(input: &str) -> Result<Vec<T>, ParseError> {
+    let bytes = input.as_bytes();
+    let mut numbers = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let is_sign = matches!(bytes[i], b'-' | b'+')
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_digit());
+
+        if is_sign || bytes[i].is_ascii_digit() {
+            let (n, used) = T::from_radix_10_signed_checked(&bytes[i..]);
+            numbers.push(n.ok_or_else(|| ParseError::at(input, i, ErrorKind::Overflow))?);
+            i += used;
+        } else {
+            i += 1;
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Parses a single, optionally signed integer spanning the whole token.
+/// Surrounding whitespace is ignored.
+pub fn number<T: FromRadix10SignedChecked>(token: &str) -> Result<T, ParseError> {
+    let start = token.len() - token.trim_start().len();
+    let trimmed = token.trim();
+    let bytes = trimmed.as_bytes();
+
+    match T::from_radix_10_signed_checked(bytes) {
+        (_, 0) => Err(ParseError::at(token, start, ErrorKind::ExpectedNumber)),
+        (_, used) if !bytes[..used].iter().any(u8::is_ascii_digit) => {
+            Err(ParseError::at(token, start, ErrorKind::ExpectedNumber))
+        }
+        (None, _) => Err(ParseError::at(token, start, ErrorKind::Overflow)),
+        (Some(_), used) if used < bytes.len() => Err(ParseError::at(
+            token,
+            start + used,
+            ErrorKind::Unexpected(trimmed[used..].chars().next().unwrap_or_default()),
+        )),
+        (Some(n), _) => Ok(n),
+    }
+}
+
+/// Parses a comma separated list of numbers, such as `3,5,4,7`.
+pub fn list<T: FromRadix10SignedChecked>(input: &str) -> Result<Vec<T>, ParseError> {
+    let mut offset = 0;
+    input
+        .trim_end()
+        .split(',')
+        .map(|token| {
+            let column = offset + 1;
+            offset += token.len() + 1;
+            number(token).map_err(|e| e.within(1, column))
+        })
+        .collect()
+}
+
+/// Parses an inclusive range of unsigned numbers, such as `10-14`.
+pub fn range<T: FromRadix10Checked>(input: &str) -> Result<(T, T), ParseError> {
+    let start = input.len() - input.trim_start().len();
+    let trimmed = input.trim();
+    let bytes = trimmed.as_bytes();
+    let at = |offset, kind| ParseError::at(input, start + offset, kind);
+
+    let parse = |offset: usize| match T::from_radix_10_checked(&bytes[offset..]) {
+        (_, 0) => Err(at(offset, ErrorKind::ExpectedNumber)),
+        (None, _) => Err(at(offset, ErrorKind::Overflow)),
+        (Some(n), used) => Ok((n, offset + used)),
+    };
+
+    let (from, i) = parse(0)?;
+    if bytes.get(i) != Some(&b'-') {
+        return Err(at(i, ErrorKind::Expected('-')));
+    }
+    let (to, i) = parse(i + 1)?;
+    if let Some(c) = trimmed[i..].chars().next() {
+        return Err(at(i, ErrorKind::Unexpected(c)));
+    }
+
+    Ok((from, to))
+}
+
+/// Parses ranges separated by commas or line breaks, such as `11-22,95-115`.
+pub fn ranges<T: FromRadix10Checked>(input: &str) -> Result<Vec<(T, T)>, ParseError> {
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+
+    for token in input.split_inclusive([',', '\n']) {
+        let content = token.trim_end_matches([',', '\n', '\r']);
+        if !content.trim().is_empty() {
+            let (line, column) = locate(input, offset);
+            ranges.push(range(content).map_err(|e| e.within(line, column))?);
+        }
+        offset += token.len();
+    }
+
+    Ok(ranges)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A bracketed token, such as `[.##.]`, `(1,3)` or `{3,5,4,7}`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Group<'a> {
+    /// The opening delimiter.
+    pub open: char,
+    /// The text between the delimiters.
+    pub contents: &'a str,
+    /// The 1-based column of the first character of `contents`.
+    pub column: usize,
+}
+
+impl Group<'_> {
+    /// Parses the contents as a comma separated list of numbers.
+    pub fn numbers<T: FromRadix10SignedChecked>(&self) -> Result<Vec<T>, ParseError> {
+        list(self.contents).map_err(|e| e.within(1, self.column))
+    }
+}
+
+fn get_closing(open: u8) -> Option<u8> {
+    match open {
+        b'[' => Some(b']'),
+        b'(' => Some(b')'),
+        b'{' => Some(b'}'),
+        b'<' => Some(b'>'),
+        _ => None,
+    }
+}
+
+/// Splits a line into whitespace separated, bracketed groups. Groups do not nest.
+pub fn groups(line: &str) -> Result<Vec<Group<'_>>, ParseError> {
+    let bytes = line.as_bytes();
+    let mut groups = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i].is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let Some(close) = get_closing(bytes[i]) else {
+            let c = line[i..].chars().next().unwrap_or_default();
+            return Err(ParseError::at(line, i, ErrorKind::Unexpected(c)));
+        };
+
+        let Some(len) = bytes[i + 1..].iter().position(|&b| b == close) else {
+            return Err(ParseError::at(
+                line,
+                line.len(),
+                ErrorKind::Unclosed(char::from(close)),
+            ));
+        };
+
+        groups.push(Group {
+            open: char::from(bytes[i]),
+            contents: &line[i + 1..i + 1 + len],
+            column: i + 2,
+        });
+        i += len + 2;
+    }
+
+    Ok(groups)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_unsigned() {
+        assert_eq!(unsigned::<u32>("x=12, y=-3: 456"), Ok(vec![12, 3, 456]));
+        assert_eq!(unsigned::<u8>("no numbers"), Ok(vec![]));
+        assert_eq!(
+            unsigned::<u8>("1\n 300"),
+            Err(ParseError {
+                line: 2,
+                column: 2,
+                kind: ErrorKind::Overflow
+            })
+        );
+    }
+
+    #[test]
+    fn extracts_signed() {
+        assert_eq!(signed::<i32>("x=12, y=-3: +4"), Ok(vec![12, -3, 4]));
+        assert_eq!(signed::<i32>("3-5 -7"), Ok(vec![3, 5, -7]));
+        assert_eq!(signed::<i64>("a - 1"), Ok(vec![1]));
+    }
+
+    #[test]
+    fn parses_number() {
+        assert_eq!(number::<i32>(" -42 "), Ok(-42));
+        assert_eq!(
+            number::<u8>("12a").unwrap_err().kind,
+            ErrorKind::Unexpected('a')
+        );
+        assert_eq!(
+            number::<u8>("-").unwrap_err().kind,
+            ErrorKind::ExpectedNumber
+        );
+        assert_eq!(number::<u8>("256").unwrap_err().kind, ErrorKind::Overflow);
+        assert_eq!(
+            number::<u32>("12µs").unwrap_err().kind,
+            ErrorKind::Unexpected('µ')
+        );
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "1-3\n5-8\n\n\n1\r\n2\r\n\r\n3\n";
+        assert_eq!(sections(input), vec!["1-3\n5-8", "1\r\n2", "3"]);
+        assert!(sections("\n\n").is_empty());
+    }
+
+    #[test]
+    fn parses_lists() {
+        assert_eq!(list::<u32>("3,5, 4,7\n"), Ok(vec![3, 5, 4, 7]));
+        assert_eq!(
+            list::<u32>("3,5,x"),
+            Err(ParseError {
+                line: 1,
+                column: 5,
+                kind: ErrorKind::ExpectedNumber
+            })
+        );
+    }
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!(range::<u64>("10-14"), Ok((10, 14)));
+        assert_eq!(
+            range::<u64>("10").unwrap_err().kind,
+            ErrorKind::Expected('-')
+        );
+        assert_eq!(
+            range::<u64>("10-14→").unwrap_err().kind,
+            ErrorKind::Unexpected('→')
+        );
+        assert_eq!(
+            ranges::<u64>("11-22,95-115\n3-5\n"),
+            Ok(vec![(11, 22), (95, 115), (3, 5)])
+        );
+        assert_eq!(
+            ranges::<u64>("1-2\n3-4,5-x"),
+            Err(ParseError {
+                line: 2,
+                column: 7,
+                kind: ErrorKind::ExpectedNumber
+            })
+        );
+    }
+
+    #[test]
+    fn parses_groups() {
+        let groups = groups("[.##.] (1,3) {3,5}").unwrap();
+        assert_eq!(groups.len(), 3);
+        assert_eq!((groups[0].open, groups[0].contents), ('[', ".##."));
+        assert_eq!(groups[1].numbers::<u8>(), Ok(vec![1, 3]));
+        assert_eq!(groups[2].numbers::<u8>(), Ok(vec![3, 5]));
+    }
+
+    #[test]
+    fn reports_group_errors() {
+        assert_eq!(
+            super::groups("[.#] (1,x)").unwrap()[1].numbers::<u8>(),
+            Err(ParseError {
+                line: 1,
+                column: 9,
+                kind: ErrorKind::ExpectedNumber
+            })
+        );
+        assert_eq!(
+            super::groups("[.#] (1").unwrap_err().kind,
+            ErrorKind::Unclosed(')')
+        );
+        assert_eq!(
+            super::groups("[.#] x").unwrap_err(),
+            ParseError {
+                line: 1,
+                column: 6,
+                kind: ErrorKind::Unexpected('x')
+            }
+        );
+    }
+
+    #[test]
+    fn reports_line_of_errors() {
+        let result = lines("1,2\n3,4\n5,y", list::<u8>);
+        assert_eq!(
+            result,
+            Err(ParseError {
+                line: 3,
+                column: 3,
+                kind: ErrorKind::ExpectedNumber
+            })
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 3, column 3: expected a number"
+        );
+    }
+}
@@ -0,0 +1,273 @@
+/// A static k-d tree over `IVec3` points for nearest neighbor queries, and for visiting
+/// point pairs from the closest upwards without materializing all `n²` pairs.
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use glam::IVec3;
+
+/// Returns the squared euclidean distance, which orders like the distance but stays exact.
+pub fn distance_squared(a: IVec3, b: IVec3) -> i64 {
+    let d = (a.as_i64vec3() - b.as_i64vec3()).abs();
+    d.x * d.x + d.y * d.y + d.z * d.z
+}
+
+/// Points are referred to by their index in the slice the tree was built from.
+/// Ties in distance are broken by the lower index.
+#[derive(Clone, Debug)]
+pub struct KdTree {
+    points: Vec<IVec3>,
+    /// Point indices arranged as an implicit tree: the median of every range is the
+    /// node, split along the axis of its depth, with the halves as subtrees.
+    nodes: Vec<usize>,
+}
+
+impl KdTree {
+    pub fn new(points: &[IVec3]) -> Self {
+        let mut nodes: Vec<usize> = (0..points.len()).collect();
+        Self::build(points, &mut nodes, 0);
+        KdTree {
+            points: points.to_vec(),
+            nodes,
+        }
+    }
+
+    fn build(points: &[IVec3], nodes: &mut [usize], depth: usize) {
+        if nodes.len() <= 1 {
+            return;
+        }
+        let mid = nodes.len() / 2;
+        nodes.select_nth_unstable_by_key(mid, |&i| points[i][depth % 3]);
+        let (left, right) = nodes.split_at_mut(mid);
+        Self::build(points, left, depth + 1);
+        Self::build(points, &mut right[1..], depth + 1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn points(&self) -> &[IVec3] {
+        &self.points
+    }
+
+    /// Returns the `k` points closest to `query` as `(index, squared distance)`,
+    /// closest first. A point at `query` itself is included.
+    pub fn nearest(&self, query: IVec3, k: usize) -> Vec<(usize, i64)> {
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search(query, k, &self.nodes, 0, &mut best);
+        }
+        best.into_sorted_vec()
+            .into_iter()
+            .map(|(distance, index)| (index, distance))
+            .collect()
+    }
+
+    /// Collects the `k` closest points into a max-heap of `(distance, index)`.
+    fn search(
+        &self,
+        query: IVec3,
+        k: usize,
+        nodes: &[usize],
+        depth: usize,
+        best: &mut BinaryHeap<(i64, usize)>,
+    ) {
+        if nodes.is_empty() {
+            return;
+        }
+
+        let mid = nodes.len() / 2;
+        let index = nodes[mid];
+        let point = self.points[index];
+
+        let candidate = (distance_squared(query, point), index);
+        if best.len() < k {
+            best.push(candidate);
+        } else if best.peek().is_some_and(|&worst| candidate < worst) {
+            best.pop();
+            best.push(candidate);
+        }
+
+        let axis = depth % 3;
+        let offset = i64::from(query[axis]) - i64::from(point[axis]);
+        let (near, far) = if offset < 0 {
+            (&nodes[..mid], &nodes[mid + 1..])
+        } else {
+            (&nodes[mid + 1..], &nodes[..mid])
+        };
+
+        self.search(query, k, near, depth + 1, best);
+        // The far side can only hold closer points if the splitting plane is in reach.
+        if best.len() < k
+            || best
+                .peek()
+                .is_some_and(|&(worst, _)| offset * offset <= worst)
+        {
+            self.search(query, k, far, depth + 1, best);
+        }
+    }
+
+    /// Iterates all pairs `(i, j, squared distance)` with `i < j`, closest first and
+    /// ties ordered by `i` and `j`. Neighbors are fetched lazily per point, so taking
+    /// the first `m` pairs costs far less than sorting all of them.
+    pub fn pairs_by_distance(&self) -> PairsByDistance<'_> {
+        let mut pairs = PairsByDistance {
+            tree: self,
+            heap: BinaryHeap::with_capacity(self.len()),
+            neighbors: vec![Vec::new(); self.len()],
+            next: vec![0; self.len()],
+        };
+        for i in 0..self.len() {
+            pairs.push_next(i);
+        }
+        pairs
+    }
+}
+
+/// The iterator returned by [`KdTree::pairs_by_distance`].
+pub struct PairsByDistance<'a> {
+    tree: &'a KdTree,
+    /// The next unvisited neighbor of every point, as `(distance, point, neighbor)`.
+    heap: BinaryHeap<Reverse<(i64, usize, usize)>>,
+    /// The neighbors fetched so far for every point, closest first.
+    neighbors: Vec<Vec<(usize, i64)>>,
+    next: Vec<usize>,
+}
+
+impl PairsByDistance<'_> {
+    const INITIAL_BATCH: usize = 8;
+
+    /// Queues the next neighbor of point `i`, fetching twice as many if needed.
+    fn push_next(&mut self, i: usize) {
+        loop {
+            let fetched = self.neighbors[i].len();
+            if self.next[i] == fetched {
+                if fetched == self.tree.len() {
+                    return;
+                }
+                let k = (2 * fetched).max(Self::INITIAL_BATCH);
+                self.neighbors[i] = self.tree.nearest(self.tree.points[i], k);
+            }
+
+            // The point itself is among its nearest neighbors, possibly last in a batch.
+            let (j, distance) = self.neighbors[i][self.next[i]];
+            self.next[i] += 1;
+            if j != i {
+                self.heap.push(Reverse((distance, i, j)));
+                return;
+            }
+        }
+    }
+}
+
+impl Iterator for PairsByDistance<'_> {
+    type Item = (usize, usize, i64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Every pair is queued from both ends, only the one from the lower index counts.
+        loop {
+            let Reverse((distance, i, j)) = self.heap.pop()?;
+            self.push_next(i);
+            if i < j {
+                return Some((i, j, distance));
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use glam::IVec3;
+
+    use super::{KdTree, distance_squared};
+
+    fn get_points() -> Vec<IVec3> {
+        // A small deterministic pseudo-random cloud with duplicates and ties.
+        let mut seed = 7u32;
+        let mut next = move || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            ((seed >> 16) % 21) as i32 - 10
+        };
+        (0..60)
+            .map(|_| IVec3::new(next(), next(), next()))
+            .collect()
+    }
+
+    fn get_all_pairs(points: &[IVec3]) -> Vec<(usize, usize, i64)> {
+        let mut pairs = Vec::new();
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                pairs.push((i, j, distance_squared(points[i], points[j])));
+            }
+        }
+        pairs.sort_by_key(|&(i, j, d)| (d, i, j));
+        pairs
+    }
+
+    #[test]
+    fn measures_distance() {
+        // The difference along x does not fit into an `i32`.
+        let a = IVec3::new(-2_000_000_000, 0, 3);
+        let b = IVec3::new(1_000_000_000, 0, -1);
+        assert_eq!(distance_squared(a, b), 9_000_000_000_000_000_016);
+    }
+
+    #[test]
+    fn finds_nearest_points() {
+        let points = get_points();
+        let tree = KdTree::new(&points);
+
+        for query in [IVec3::ZERO, IVec3::new(10, -10, 3), points[5]] {
+            let mut expected: Vec<(usize, i64)> = (0..points.len())
+                .map(|i| (i, distance_squared(query, points[i])))
+                .collect();
+            expected.sort_by_key(|&(i, d)| (d, i));
+
+            for k in [0, 1, 5, 60, 100] {
+                let expected = &expected[..k.min(points.len())];
+                assert_eq!(tree.nearest(query, k), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn streams_pairs_by_distance() {
+        let points = get_points();
+        let tree = KdTree::new(&points);
+        assert_eq!(
+            tree.pairs_by_distance().collect::<Vec<_>>(),
+            get_all_pairs(&points)
+        );
+
+        // The last duplicate finds itself last in its first batch of neighbors.
+        let mut points = vec![IVec3::ZERO; 8];
+        points.push(IVec3::new(5, 0, 0));
+        let tree = KdTree::new(&points);
+        assert_eq!(
+            tree.pairs_by_distance().collect::<Vec<_>>(),
+            get_all_pairs(&points)
+        );
+    }
+
+    #[test]
+    fn handles_tiny_trees() {
+        assert!(KdTree::new(&[]).pairs_by_distance().next().is_none());
+        assert!(
+            KdTree::new(&[IVec3::ONE])
+                .pairs_by_distance()
+                .next()
+                .is_none()
+        );
+        assert_eq!(
+            KdTree::new(&[IVec3::ONE, IVec3::ZERO])
+                .pairs_by_distance()
+                .collect::<Vec<_>>(),
+            vec![(0, 1, 3)]
+        );
+    }
+}
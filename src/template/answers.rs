@@ -0,0 +1,202 @@
+/// Module that records the answers submitted with `cargo solve --submit`.
+/// Answers are verified against the accepted answers in the downloaded puzzle description.
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, data_dir};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+fn get_answers_path() -> PathBuf {
+    data_dir().join(ANSWERS_FILE_NAME)
+}
+
+/// The last answer submitted for a part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub day: Day,
+    pub part: u8,
+    pub value: String,
+}
+
+/// Represents the submitted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_answers_path())?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(get_answers_path())
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|a| a.day == day && a.part == part)
+            .map(|a| a.value.as_str())
+    }
+
+    /// Records an answer, replacing a previous answer for the same part.
+    pub fn record(&mut self, day: Day, part: u8, value: String) {
+        match self
+            .data
+            .iter_mut()
+            .find(|a| a.day == day && a.part == part)
+        {
+            Some(answer) => answer.value = value,
+            None => {
+                self.data.push(Answer { day, part, value });
+                self.data.sort_unstable_by_key(|a| (a.day, a.part));
+            }
+        }
+    }
+}
+
+/// Extracts the answers that Advent of Code accepted from a puzzle description
+/// downloaded by aoc-cli, in part order.
+#[must_use]
+pub fn parse_accepted_answers(puzzle: &str) -> Vec<String> {
+    puzzle
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once("Your puzzle answer was")?;
+            let answer = rest.trim().trim_end_matches('.').trim_matches('`').trim();
+            (!answer.is_empty()).then(|| answer.to_string())
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("value".into(), JsonValue::String(value.value.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| **part == 1.0 || **part == 2.0)
+            .ok_or("Expected answer.part to be 1 or 2.")?;
+
+        let value = json
+            .get("value")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected answer.value to be a string.")?;
+
+        Ok(Answer {
+            day,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            part: *part as u8,
+            value: value.clone(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, parse_accepted_answers};
+    use crate::day;
+
+    #[test]
+    fn roundtrips_json() {
+        let mut answers = Answers::default();
+        answers.record(day!(2), 1, "1227775554".into());
+        answers.record(day!(1), 2, "6".into());
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn replaces_recorded_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "2".into());
+        answers.record(day!(1), 1, "3".into());
+        assert_eq!(answers.data.len(), 1);
+        assert_eq!(answers.get(day!(1), 1), Some("3"));
+        assert_eq!(answers.get(day!(1), 2), None);
+    }
+
+    #[test]
+    fn parses_accepted_answers() {
+        let puzzle = "\\--- Day 1: Secret Entrance ---\n\nYour puzzle answer was `1064`.\n\n\\--- Part Two ---\n\nYour puzzle answer was `6122`.\n";
+        assert_eq!(parse_accepted_answers(puzzle), vec!["1064", "6122"]);
+        assert!(parse_accepted_answers("no answers yet").is_empty());
+    }
+}
@@ -4,7 +4,7 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{Day, checksums, data_dir};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -46,7 +46,12 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
         day,
     );
 
-    call_aoc_cli(&args)
+    let output = call_aoc_cli(&args)?;
+
+    #[cfg(feature = "encrypted-inputs")]
+    seal(&get_puzzle_path(day));
+
+    Ok(output)
 }
 
 pub fn download(day: Day) -> Result<Output, AocCommandError> {
@@ -69,6 +74,17 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     println!("---");
     println!("🎄 Successfully wrote input to \"{}\".", &input_path);
     println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+
+    if let Err(e) = checksums::record(&format!("inputs/{day}.txt")) {
+        eprintln!("Failed to record input checksum: {e}");
+    }
+
+    #[cfg(feature = "encrypted-inputs")]
+    {
+        seal(&input_path);
+        seal(&puzzle_path);
+    }
+
     Ok(output)
 }
 
@@ -80,15 +96,32 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     call_aoc_cli(&args)
 }
 
+/// Replaces a plaintext file written by aoc-cli with its encrypted counterpart.
+#[cfg(feature = "encrypted-inputs")]
+fn seal(path: &str) {
+    match crate::template::crypto::seal_file(std::path::Path::new(path)) {
+        Ok(sealed_path) => println!("🔒 Encrypted \"{path}\" to \"{}\".", sealed_path.display()),
+        Err(e) => eprintln!("Failed to encrypt \"{path}\": {e}"),
+    }
+}
+
 fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    data_dir()
+        .join("inputs")
+        .join(format!("{day}.txt"))
+        .display()
+        .to_string()
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    data_dir()
+        .join("puzzles")
+        .join(format!("{day}.md"))
+        .display()
+        .to_string()
 }
 
-fn get_year() -> Option<u16> {
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
@@ -0,0 +1,192 @@
+/// Module that records checksums of downloaded inputs, so that later reads can detect
+/// inputs that were edited, truncated or converted to Windows line endings.
+use std::{collections::HashMap, fmt::Display, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::data_dir;
+
+static MANIFEST_FILE_NAME: &str = "checksums.json";
+
+/// Fingerprint of a file in the data store.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checksum {
+    pub hash: u64,
+    pub len: usize,
+}
+
+impl Checksum {
+    pub fn new(contents: &[u8]) -> Self {
+        Self {
+            hash: fnv1a(contents),
+            len: contents.len(),
+        }
+    }
+}
+
+/// 64-bit FNV-1a, which is stable across Rust versions unlike the std hasher.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// A problem detected while reading a file from the data store.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Warning {
+    Edited,
+    Truncated { expected: usize, actual: usize },
+    WindowsLineEndings,
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::Edited => write!(f, "was edited after it was downloaded."),
+            Warning::Truncated { expected, actual } => write!(
+                f,
+                "looks truncated: expected {expected} bytes, found {actual}."
+            ),
+            Warning::WindowsLineEndings => write!(f, "has Windows (CRLF) line endings."),
+        }
+    }
+}
+
+/// Compares file contents against the checksum recorded when the file was downloaded.
+pub fn check(expected: Option<&Checksum>, contents: &str) -> Vec<Warning> {
+    let mut warnings = vec![];
+
+    if let Some(expected) = expected {
+        let actual = Checksum::new(contents.as_bytes());
+        if actual.len < expected.len {
+            warnings.push(Warning::Truncated {
+                expected: expected.len,
+                actual: actual.len,
+            });
+        } else if actual != *expected {
+            warnings.push(Warning::Edited);
+        }
+    }
+
+    if contents.contains("\r\n") {
+        warnings.push(Warning::WindowsLineEndings);
+    }
+
+    warnings
+}
+
+/// Checks the contents of `path` (relative to the data root) against the manifest.
+pub fn verify(path: &str, contents: &str) -> Vec<Warning> {
+    let manifest = read_manifest();
+    check(manifest.get(path), contents)
+}
+
+/// Records the checksum of the file at `path` (relative to the data root) in the manifest.
+pub fn record(path: &str) -> Result<(), io::Error> {
+    let contents = fs::read(data_dir().join(path))?;
+    let mut manifest = read_manifest();
+    manifest.insert(path.to_string(), Checksum::new(&contents));
+    store_manifest(&manifest)
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn read_manifest() -> HashMap<String, Checksum> {
+    fs::read_to_string(data_dir().join(MANIFEST_FILE_NAME))
+        .ok()
+        .and_then(|s| parse_manifest(&s))
+        .unwrap_or_default()
+}
+
+fn parse_manifest(s: &str) -> Option<HashMap<String, Checksum>> {
+    let json = JsonValue::from_str(s).ok()?;
+
+    json.get::<HashMap<String, JsonValue>>()?
+        .iter()
+        .map(|(path, value)| {
+            let entry = value.get::<HashMap<String, JsonValue>>()?;
+            let hash = entry
+                .get("fnv1a")
+                .and_then(|v| v.get::<String>())
+                .and_then(|v| u64::from_str_radix(v, 16).ok())?;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let len = *entry.get("bytes").and_then(|v| v.get::<f64>())? as usize;
+            Some((path.clone(), Checksum { hash, len }))
+        })
+        .collect()
+}
+
+fn store_manifest(manifest: &HashMap<String, Checksum>) -> Result<(), io::Error> {
+    let json = JsonValue::Object(
+        manifest
+            .iter()
+            .map(|(path, checksum)| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert(
+                    "fnv1a".into(),
+                    JsonValue::String(format!("{:016x}", checksum.hash)),
+                );
+                map.insert("bytes".into(), JsonValue::Number(checksum.len as f64));
+                (path.clone(), JsonValue::Object(map))
+            })
+            .collect(),
+    );
+
+    let mut file = fs::File::create(data_dir().join(MANIFEST_FILE_NAME))?;
+    json.format_to(&mut file)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Checksum, Warning, check, parse_manifest};
+
+    #[test]
+    fn accepts_unchanged_contents() {
+        let checksum = Checksum::new(b"1\n2\n3\n");
+        assert_eq!(check(Some(&checksum), "1\n2\n3\n"), vec![]);
+    }
+
+    #[test]
+    fn accepts_unknown_contents() {
+        assert_eq!(check(None, "1\n2\n3\n"), vec![]);
+    }
+
+    #[test]
+    fn detects_edited_contents() {
+        let checksum = Checksum::new(b"1\n2\n3\n");
+        assert_eq!(check(Some(&checksum), "1\n5\n3\n"), vec![Warning::Edited]);
+    }
+
+    #[test]
+    fn detects_truncated_contents() {
+        let checksum = Checksum::new(b"1\n2\n3\n");
+        assert_eq!(
+            check(Some(&checksum), "1\n2\n"),
+            vec![Warning::Truncated {
+                expected: 6,
+                actual: 4
+            }]
+        );
+    }
+
+    #[test]
+    fn detects_windows_line_endings() {
+        let checksum = Checksum::new(b"1\n2\n");
+        assert_eq!(
+            check(Some(&checksum), "1\r\n2\r\n"),
+            vec![Warning::Edited, Warning::WindowsLineEndings]
+        );
+    }
+
+    #[test]
+    fn parses_manifest() {
+        let manifest =
+            parse_manifest(r#"{ "inputs/01.txt": { "fnv1a": "00000000000000ff", "bytes": 12 } }"#)
+                .unwrap();
+        assert_eq!(
+            manifest.get("inputs/01.txt"),
+            Some(&Checksum { hash: 255, len: 12 })
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use std::{path::Path, process};
+
+use crate::template::leaderboard::{Leaderboard, construct_tables, update_readme};
+
+pub enum Source {
+    Import(String),
+    Download(String),
+}
+
+pub fn handle(source: Option<Source>, me: Option<&str>, markdown: bool) {
+    let result = match &source {
+        Some(Source::Import(path)) => Leaderboard::import(Path::new(path)),
+        Some(Source::Download(id)) => Leaderboard::download(id),
+        None => Leaderboard::read_from_file(),
+    };
+
+    let leaderboard = match result {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("Failed to load leaderboard: {e}");
+            if source.is_none() {
+                eprintln!("Import one with `--import <path>` or `--download <id>` first.");
+            }
+            process::exit(1);
+        }
+    };
+
+    if source.is_some() {
+        println!(
+            "🎄 Stored leaderboard with {} member(s).",
+            leaderboard.members.len()
+        );
+    }
+
+    // personal times default to the owner of the leaderboard.
+    let member = match me {
+        Some(me) => match leaderboard.get_member(me) {
+            Some(member) => Some(member),
+            None => {
+                eprintln!("Member \"{me}\" is not on the leaderboard.");
+                process::exit(1);
+            }
+        },
+        None => leaderboard
+            .members
+            .iter()
+            .find(|m| m.id == leaderboard.owner_id),
+    };
+
+    let tables = construct_tables(&leaderboard, member);
+
+    if markdown {
+        match update_readme(&tables) {
+            Ok(()) => println!("Stored updated leaderboard."),
+            Err(e) => {
+                eprintln!("Failed to store updated leaderboard: {e}");
+                process::exit(1);
+            }
+        }
+    } else {
+        // print the tables without the surrounding markers.
+        for line in tables.lines().filter(|line| !line.starts_with("<!---")) {
+            println!("{line}");
+        }
+    }
+}
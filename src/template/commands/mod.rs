@@ -1,6 +1,13 @@
 pub mod all;
 pub mod download;
+pub mod leaderboard;
 pub mod read;
+#[cfg(feature = "encrypted-inputs")]
+pub mod rekey;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
+pub mod watch;
@@ -0,0 +1,39 @@
+use std::{fs, process};
+
+use crate::template::{
+    crypto::{KEY_ENV_VAR, Key, NEW_KEY_ENV_VAR, rekey_files},
+    data_dir,
+};
+
+/// Re-encrypts every `.enc` file in the data store, from the key in `AOC_INPUT_KEY`
+/// to the key in `AOC_INPUT_NEW_KEY`.
+pub fn handle() {
+    let (old, new) = match (Key::from_env(KEY_ENV_VAR), Key::from_env(NEW_KEY_ENV_VAR)) {
+        (Ok(old), Ok(new)) => (old, new),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("Failed to read keys: {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut paths = Vec::new();
+    for folder in ["inputs", "puzzles"] {
+        let Ok(entries) = fs::read_dir(data_dir().join(folder)) else {
+            continue;
+        };
+        paths.extend(
+            entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "enc")),
+        );
+    }
+
+    if let Err((path, e)) = rekey_files(&paths, &old, &new) {
+        eprintln!("Failed to re-key \"{}\": {e}", path.display());
+        eprintln!("No files were changed, all of them are still encrypted with {KEY_ENV_VAR}.");
+        process::exit(1);
+    }
+
+    println!("🔑 Re-keyed {} file(s).", paths.len());
+    println!("Set {KEY_ENV_VAR} to the value of {NEW_KEY_ENV_VAR} to keep reading them.");
+}
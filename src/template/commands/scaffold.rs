@@ -1,75 +1,308 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    ffi::OsString,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
     process,
 };
 
-use crate::template::Day;
+use crate::template::{
+    Day, aoc_cli, data_dir, data_file_exists, get_data_file_paths, get_day_count, read_to_string,
+    registry::Registry,
+};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
+/// Directory holding user-defined templates, selected by file name with `--template`.
+/// Resolved against the crate root, like [`data_dir`].
+fn templates_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("templates")
+}
+
+/// What scaffolding did (or would do) to a generated file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    Created,
+    Skipped,
+    Overwritten,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Created => f.write_str("created"),
+            Status::Skipped => f.write_str("skipped (exists, pass --overwrite to replace)"),
+            Status::Overwritten => f.write_str("overwritten"),
+        }
     }
-    file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+/// A file that is written by scaffolding.
+struct Generated {
+    label: &'static str,
+    path: PathBuf,
+    contents: String,
+}
+
+/// A completed write, recorded so it can be undone if a later step fails.
+enum Step {
+    Created(PathBuf),
+    Overwritten { path: PathBuf, backup: PathBuf },
+}
+
+/// Returns a path that the previous contents of an overwritten file can be moved to,
+/// `<path>.bak` or, if earlier backups exist, `<path>.bak.1`, `<path>.bak.2`, ...
+fn get_backup_path(path: &Path) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".bak");
+    let mut backup = PathBuf::from(&name);
+    let mut n = 1;
+    while backup.exists() {
+        backup = PathBuf::from(format!("{}.{n}", name.to_string_lossy()));
+        n += 1;
+    }
+    backup
+}
+
+/// Determines what writing `path` would do, without touching the file system.
+fn plan(path: &Path, overwrite: bool) -> Status {
+    match (data_file_exists(path), overwrite) {
+        (false, _) => Status::Created,
+        (true, false) => Status::Skipped,
+        (true, true) => Status::Overwritten,
+    }
+}
+
+/// Writes a generated file following the overwrite policy. Existing files are only
+/// replaced with `overwrite`, after moving them to a backup. This includes the encrypted
+/// counterpart of a data file, which would otherwise still be read instead of the new file.
+fn write_file(file: &Generated, overwrite: bool, journal: &mut Vec<Step>) -> io::Result<Status> {
+    let status = plan(&file.path, overwrite);
+
+    match status {
+        Status::Skipped => {}
+        Status::Created => create_file(file, journal)?,
+        Status::Overwritten => {
+            for path in get_data_file_paths(&file.path) {
+                let backup = get_backup_path(&path);
+                fs::rename(&path, &backup)?;
+                journal.push(Step::Overwritten { path, backup });
+            }
+            create_file(file, journal)?;
+        }
+    }
+
+    Ok(status)
+}
+
+fn create_file(file: &Generated, journal: &mut Vec<Step>) -> io::Result<()> {
+    // `create_new` guards against files that appeared since planning.
     OpenOptions::new()
         .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+        .create_new(true)
+        .open(&file.path)?
+        .write_all(file.contents.as_bytes())
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&file.path);
+        })?;
+    journal.push(Step::Created(file.path.clone()));
+    Ok(())
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Undoes the recorded steps in reverse order, restoring overwritten files from their backups.
+fn rollback(journal: Vec<Step>) {
+    for step in journal.into_iter().rev() {
+        let result = match &step {
+            Step::Created(path) => fs::remove_file(path),
+            Step::Overwritten { path, backup } => fs::rename(backup, path),
+        };
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+        let path = match &step {
+            Step::Created(path) | Step::Overwritten { path, .. } => path,
+        };
+
+        match result {
+            Ok(()) => eprintln!("Rolled back \"{}\"", path.display()),
+            Err(e) => eprintln!("Failed to roll back \"{}\": {e}", path.display()),
         }
+    }
+}
+
+/// Loads the module template: the built-in one, or `templates/<name>.txt`.
+fn load_template(name: Option<&str>) -> Result<String, String> {
+    let Some(name) = name else {
+        return Ok(MODULE_TEMPLATE.to_string());
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
+    let path = templates_dir().join(format!("{name}.txt"));
+    fs::read_to_string(&path).map_err(|_| {
+        let available = list_templates();
+        format!(
+            "template \"{name}\" not found at \"{}\". Available templates: {}",
+            path.display(),
+            if available.is_empty() {
+                "none".to_string()
+            } else {
+                available.join(", ")
+            }
+        )
+    })
+}
+
+fn list_templates() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(templates_dir()) else {
+        return vec![];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_string())
+        })
+        .collect();
+
+    names.sort_unstable();
+    names
+}
+
+/// Extracts the puzzle title from a puzzle description downloaded by aoc-cli,
+/// e.g. `Secret Entrance` from `\--- Day 1: Secret Entrance ---`.
+fn parse_puzzle_title(puzzle: &str) -> Option<String> {
+    let line = puzzle.lines().find(|line| line.contains("--- Day "))?;
+    let (_, title) = line.split_once(": ")?;
+    let title = title.trim().trim_end_matches('-').trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+fn get_puzzle_title(day: Day) -> Option<String> {
+    let puzzle_path = data_dir().join("puzzles").join(format!("{day}.md"));
+    parse_puzzle_title(&read_to_string(&puzzle_path).ok()?)
+}
+
+/// Replaces the `%PLACEHOLDER%`s of a template.
+fn render(template: &str, day: Day, title: Option<&str>, answers: &[String]) -> String {
+    let day_number = day.into_inner().to_string();
+    let title = title.map_or_else(|| format!("Day {day_number}"), str::to_string);
+    let year = aoc_cli::get_year()
+        .map(|y| y.to_string())
+        .unwrap_or_default();
+
+    let example_answer = |part: usize| {
+        answers
+            .get(part)
+            .map_or_else(|| "None".to_string(), |answer| format!("Some({answer})"))
+    };
+
+    template
+        .replace("%DAY_NUMBER%", &day_number)
+        .replace("%DAY%", &day.to_string())
+        .replace("%YEAR%", &year)
+        .replace("%PUZZLE_TITLE%", &title)
+        .replace("%EXAMPLE_ANSWER_1%", &example_answer(0))
+        .replace("%EXAMPLE_ANSWER_2%", &example_answer(1))
+}
+
+pub fn handle(
+    day: Day,
+    overwrite: bool,
+    template: Option<&str>,
+    example_answers: &[String],
+    dry_run: bool,
+) {
+    if !day.is_in_event() {
+        eprintln!(
+            "Day {day} is not part of the event, which has {} days. \
+            Set AOC_DAYS to override the number of days.",
+            get_day_count()
+        );
+        process::exit(1);
+    }
+
+    let template = match load_template(template) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            eprintln!("Failed to load template: {e}");
             process::exit(1);
         }
-    }
+    };
+
+    let title = get_puzzle_title(day);
+
+    let files = [
+        Generated {
+            label: "Module file",
+            path: Path::new("src").join("bin").join(format!("{day}.rs")),
+            contents: render(&template, day, title.as_deref(), example_answers),
+        },
+        Generated {
+            label: "Input file",
+            path: data_dir().join("inputs").join(format!("{day}.txt")),
+            contents: String::new(),
+        },
+        Generated {
+            label: "Example file",
+            path: data_dir().join("examples").join(format!("{day}.txt")),
+            contents: String::new(),
+        },
+    ];
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    if dry_run {
+        for file in &files {
+            let status = plan(&file.path, overwrite);
+            println!(
+                "Would write {} \"{}\": {status}",
+                file.label.to_lowercase(),
+                file.path.display()
+            );
+            if status != Status::Skipped && !file.contents.is_empty() {
+                println!("---");
+                print!("{}", file.contents);
+                println!("---");
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+        println!("Would register day {day} in the registry");
+        return;
+    }
+
+    let mut journal = vec![];
+
+    for file in &files {
+        let written = journal.len();
+        match write_file(file, overwrite, &mut journal) {
+            Ok(status) => {
+                println!("{} \"{}\": {status}", file.label, file.path.display());
+                for step in &journal[written..] {
+                    if let Step::Overwritten { path, backup } = step {
+                        println!(
+                            "  previous \"{}\" saved to \"{}\"",
+                            path.display(),
+                            backup.display()
+                        );
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to write {}: {e}", file.label.to_lowercase());
+                rollback(journal);
+                process::exit(1);
+            }
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    let mut registry = Registry::read_from_file();
+    registry.register(day, title);
+    match registry.store_file() {
+        Ok(()) => {
+            println!("Registered day {day}");
         }
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("Failed to update the registry: {e}");
+            rollback(journal);
             process::exit(1);
         }
     }
@@ -77,3 +310,149 @@ pub fn handle(day: Day, overwrite: bool) {
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::{
+        Generated, Status, get_backup_path, parse_puzzle_title, render, rollback, write_file,
+    };
+    use crate::day;
+
+    fn get_temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn generated(path: PathBuf, contents: &str) -> Generated {
+        Generated {
+            label: "file",
+            path,
+            contents: contents.into(),
+        }
+    }
+
+    #[test]
+    fn parses_puzzle_title() {
+        let puzzle = "\\--- Day 1: Secret Entrance ---\n----------\n\nThe Elves have good news";
+        assert_eq!(parse_puzzle_title(puzzle), Some("Secret Entrance".into()));
+        assert_eq!(
+            parse_puzzle_title("## --- Day 12: Christmas Tree Farm ---"),
+            Some("Christmas Tree Farm".into())
+        );
+        assert_eq!(parse_puzzle_title("no title here"), None);
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let rendered = render(
+            "%DAY_NUMBER% %DAY% %PUZZLE_TITLE% %EXAMPLE_ANSWER_1% %EXAMPLE_ANSWER_2%",
+            day!(3),
+            Some("Lobby"),
+            &["357".to_string()],
+        );
+        assert_eq!(rendered, "3 03 Lobby Some(357) None");
+    }
+
+    #[test]
+    fn renders_fallback_title() {
+        assert_eq!(render("%PUZZLE_TITLE%", day!(3), None, &[]), "Day 3");
+    }
+
+    #[test]
+    fn skips_existing_files() {
+        let dir = get_temp_dir("skip");
+        let file = generated(dir.join("01.txt"), "");
+        fs::write(&file.path, "puzzle input").unwrap();
+
+        let mut journal = vec![];
+        assert_eq!(
+            write_file(&file, false, &mut journal).unwrap(),
+            Status::Skipped
+        );
+        assert_eq!(fs::read_to_string(&file.path).unwrap(), "puzzle input");
+        assert!(journal.is_empty());
+    }
+
+    #[test]
+    fn backs_up_overwritten_files() {
+        let dir = get_temp_dir("overwrite");
+        let file = generated(dir.join("01.txt"), "new");
+        fs::write(&file.path, "old").unwrap();
+
+        let mut journal = vec![];
+        assert_eq!(
+            write_file(&file, true, &mut journal).unwrap(),
+            Status::Overwritten
+        );
+        assert_eq!(fs::read_to_string(&file.path).unwrap(), "new");
+        assert_eq!(fs::read_to_string(dir.join("01.txt.bak")).unwrap(), "old");
+    }
+
+    #[test]
+    fn keeps_earlier_backups() {
+        let dir = get_temp_dir("backups");
+        let file = generated(dir.join("01.txt"), "newest");
+        fs::write(&file.path, "new").unwrap();
+        fs::write(dir.join("01.txt.bak"), "old").unwrap();
+        assert_eq!(get_backup_path(&file.path), dir.join("01.txt.bak.1"));
+
+        write_file(&file, true, &mut vec![]).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("01.txt.bak")).unwrap(), "old");
+        assert_eq!(fs::read_to_string(dir.join("01.txt.bak.1")).unwrap(), "new");
+        assert_eq!(get_backup_path(&file.path), dir.join("01.txt.bak.2"));
+    }
+
+    #[cfg(feature = "encrypted-inputs")]
+    #[test]
+    fn overwrites_encrypted_files() {
+        let dir = get_temp_dir("encrypted");
+        let file = generated(dir.join("01.txt"), "");
+        let sealed = dir.join("01.txt.enc");
+        fs::write(&sealed, "sealed input").unwrap();
+
+        let mut journal = vec![];
+        assert_eq!(
+            write_file(&file, true, &mut journal).unwrap(),
+            Status::Overwritten
+        );
+        assert_eq!(fs::read_to_string(&file.path).unwrap(), "");
+        assert!(!sealed.exists());
+        assert_eq!(
+            fs::read_to_string(dir.join("01.txt.enc.bak")).unwrap(),
+            "sealed input"
+        );
+
+        rollback(journal);
+        assert!(!file.path.exists());
+        assert_eq!(fs::read_to_string(&sealed).unwrap(), "sealed input");
+    }
+
+    #[test]
+    fn rolls_back_written_files() {
+        let dir = get_temp_dir("rollback");
+        let created = generated(dir.join("01.rs"), "module");
+        let overwritten = generated(dir.join("01.txt"), "");
+        fs::write(&overwritten.path, "puzzle input").unwrap();
+
+        let mut journal = vec![];
+        assert_eq!(
+            write_file(&created, true, &mut journal).unwrap(),
+            Status::Created
+        );
+        write_file(&overwritten, true, &mut journal).unwrap();
+        rollback(journal);
+
+        assert!(!created.path.exists());
+        assert_eq!(
+            fs::read_to_string(&overwritten.path).unwrap(),
+            "puzzle input"
+        );
+        assert!(!dir.join("01.txt.bak").exists());
+    }
+}
@@ -0,0 +1,241 @@
+/// Prints a per-day overview of the progress: scaffolded modules, inputs, example tests,
+/// submitted answers, benchmarks and stars.
+use std::{
+    fs,
+    process::{self, Command, Stdio},
+};
+
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, Day, all_days,
+    answers::{Answers, parse_accepted_answers},
+    data_dir, data_file_exists, read_to_string, readme_benchmarks,
+    registry::Registry,
+    timings::Timings,
+};
+
+static MARKER: &str = "<!--- status table --->";
+
+struct DayStatus {
+    day: Day,
+    scaffolded: bool,
+    input: bool,
+    /// `None` if the day has not been scaffolded.
+    examples: Option<bool>,
+    recorded: usize,
+    verified: usize,
+    benchmark: Option<String>,
+    stars: usize,
+}
+
+pub fn handle(markdown: bool) {
+    let answers = Answers::read_from_file();
+    let timings = Timings::read_from_file();
+    let registry = Registry::read_from_file();
+
+    println!("Running example tests...");
+    let statuses: Vec<DayStatus> = all_days()
+        .map(|day| get_status(day, registry.contains(day), &answers, &timings))
+        .collect();
+
+    if markdown {
+        match update_readme(&statuses) {
+            Ok(()) => println!("Stored updated status."),
+            Err(e) => {
+                eprintln!("Failed to store updated status: {e:?}");
+                process::exit(1);
+            }
+        }
+    } else {
+        println!();
+        print!("{}", construct_text_table(&statuses));
+    }
+}
+
+fn get_status(day: Day, scaffolded: bool, answers: &Answers, timings: &Timings) -> DayStatus {
+    let input = data_file_exists(&data_dir().join("inputs").join(format!("{day}.txt")));
+
+    let accepted = read_to_string(&data_dir().join("puzzles").join(format!("{day}.md")))
+        .map(|puzzle| parse_accepted_answers(&puzzle))
+        .unwrap_or_default();
+
+    let recorded: Vec<(u8, &str)> = [1, 2]
+        .into_iter()
+        .filter_map(|part| Some((part, answers.get(day, part)?)))
+        .collect();
+
+    let verified = recorded
+        .iter()
+        .filter(|(part, value)| {
+            accepted.get(usize::from(*part) - 1).map(String::as_str) == Some(value)
+        })
+        .count();
+
+    let benchmark = timings
+        .data
+        .iter()
+        .find(|timing| timing.day == day)
+        .map(|timing| format!("{:.2}ms", timing.total_nanos / 1_000_000.0));
+
+    DayStatus {
+        day,
+        scaffolded,
+        input,
+        examples: scaffolded.then(|| run_example_tests(day)),
+        recorded: recorded.len(),
+        verified,
+        benchmark,
+        stars: accepted.len().min(2),
+    }
+}
+
+fn run_example_tests(day: Day) -> bool {
+    Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &day.to_string()])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn mark(value: bool) -> &'static str {
+    if value { "✔" } else { "✖" }
+}
+
+/// Returns the cells of a row: scaffolded, input, examples, answers, benchmark and stars.
+fn get_cells(status: &DayStatus) -> [String; 6] {
+    [
+        mark(status.scaffolded).into(),
+        mark(status.input).into(),
+        status.examples.map_or("-", mark).into(),
+        format!("{}/{}", status.recorded, status.verified),
+        status.benchmark.clone().unwrap_or_else(|| "-".into()),
+        "★".repeat(status.stars),
+    ]
+}
+
+fn construct_text_table(statuses: &[DayStatus]) -> String {
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}{:<4}{:>11}{:>7}{:>10}{:>9}{:>12}{:>7}{ANSI_RESET}",
+        "Day", "Scaffolded", "Input", "Examples", "Answers", "Benchmark", "Stars"
+    )];
+
+    for status in statuses {
+        let [scaffolded, input, examples, answers, benchmark, stars] = get_cells(status);
+        lines.push(format!(
+            "{:<4}{scaffolded:>11}{input:>7}{examples:>10}{answers:>9}{benchmark:>12}{stars:>7}",
+            status.day.to_string()
+        ));
+    }
+
+    let stars: usize = statuses.iter().map(|s| s.stars).sum();
+    lines.push(String::new());
+    lines.push(format!(
+        "Answers are shown as recorded/verified. Total: {stars}★"
+    ));
+    lines.push(String::new());
+
+    lines.join("\n")
+}
+
+fn construct_markdown_table(statuses: &[DayStatus]) -> String {
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        "## Status".into(),
+        String::new(),
+        "| Day | Scaffolded | Input | Examples | Answers | Benchmark | Stars |".into(),
+        "| :---: | :---: | :---: | :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for status in statuses {
+        let [scaffolded, input, examples, answers, benchmark, stars] = get_cells(status);
+        let day = if status.scaffolded {
+            format!(
+                "[Day {}]({})",
+                status.day.into_inner(),
+                readme_benchmarks::get_path_for_bin(status.day)
+            )
+        } else {
+            format!("Day {}", status.day.into_inner())
+        };
+        lines.push(format!(
+            "| {day} | {scaffolded} | {input} | {examples} | {answers} | `{benchmark}` | {stars} |"
+        ));
+    }
+
+    let stars: usize = statuses.iter().map(|s| s.stars).sum();
+    lines.push(String::new());
+    lines.push(format!("**Total: {stars}★**"));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_readme(statuses: &[DayStatus]) -> Result<(), readme_benchmarks::Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    readme_benchmarks::replace_section(&mut readme, MARKER, &construct_markdown_table(statuses))?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DayStatus, MARKER, construct_markdown_table, construct_text_table, get_cells};
+    use crate::day;
+
+    fn get_mock_statuses() -> Vec<DayStatus> {
+        vec![
+            DayStatus {
+                day: day!(1),
+                scaffolded: true,
+                input: true,
+                examples: Some(true),
+                recorded: 2,
+                verified: 2,
+                benchmark: Some("0.04ms".into()),
+                stars: 2,
+            },
+            DayStatus {
+                day: day!(2),
+                scaffolded: false,
+                input: false,
+                examples: None,
+                recorded: 0,
+                verified: 0,
+                benchmark: None,
+                stars: 0,
+            },
+        ]
+    }
+
+    #[test]
+    fn formats_cells() {
+        let statuses = get_mock_statuses();
+        assert_eq!(
+            get_cells(&statuses[0]),
+            ["✔", "✔", "✔", "2/2", "0.04ms", "★★"]
+        );
+        assert_eq!(get_cells(&statuses[1]), ["✖", "✖", "-", "0/0", "-", ""]);
+    }
+
+    #[test]
+    fn constructs_markdown_table() {
+        let table = construct_markdown_table(&get_mock_statuses());
+        assert!(table.starts_with(MARKER) && table.ends_with(MARKER));
+        assert!(table.contains("| [Day 1](./src/bin/01.rs) | ✔ | ✔ | ✔ | 2/2 | `0.04ms` | ★★ |"));
+        assert!(table.contains("| Day 2 | ✖ | ✖ | - | 0/0 | `-` |  |"));
+        assert!(table.contains("**Total: 2★**"));
+    }
+
+    #[test]
+    fn constructs_text_table() {
+        let table = construct_text_table(&get_mock_statuses());
+        assert_eq!(table.lines().count(), 5);
+        assert!(table.contains("Total: 2★"));
+    }
+}
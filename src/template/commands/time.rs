@@ -1,10 +1,20 @@
 use std::collections::HashSet;
+use std::process::{self, Command, Stdio};
 
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
 use crate::template::{Day, all_days, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(day: Option<Day>, run_all: bool, store: bool, scale: bool, part: Option<u8>) {
+    if scale {
+        let Some(day) = day else {
+            eprintln!("`--scale` requires a day. Format: cargo time 1 --scale");
+            process::exit(1);
+        };
+        handle_scale(day, part);
+        return;
+    }
+
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -14,17 +24,24 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| match part {
+                        Some(part) => !stored_timings.is_part_complete(*day, part),
+                        None => !stored_timings.is_day_complete(*day),
+                    })
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, part).unwrap();
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
+        // when a single part was run, keep the stored timing of the other part.
+        let merged_timings = match part {
+            Some(part) => stored_timings.merge_part(&timings, part),
+            None => stored_timings.merge(&timings),
+        };
         merged_timings.store_file().unwrap();
 
         println!();
@@ -38,3 +55,32 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Runs the solution bin on generated inputs of increasing size and fits complexity curves to
+/// the timings.
+fn handle_scale(day: Day, part: Option<u8>) {
+    let day_padded = day.to_string();
+    let part_str = part.map(|part| part.to_string());
+    let mut args = vec![
+        "run",
+        "--quiet",
+        "--release",
+        "--bin",
+        &day_padded,
+        "--",
+        "--scale",
+    ];
+
+    if let Some(part_str) = &part_str {
+        args.extend(["--part", part_str]);
+    }
+
+    let mut cmd = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
+}
@@ -0,0 +1,168 @@
+use std::{
+    io::{Write, stdout},
+    process, thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chrono::{Datelike, FixedOffset, Utc};
+
+use crate::template::{
+    Day, SERVER_UTC_OFFSET, all_days, aoc_cli,
+    commands::{download, read, scaffold},
+    format_countdown,
+};
+
+/// How often the download is attempted once the puzzle unlocked, the server
+/// may take a moment until the input is available.
+const MAX_ATTEMPTS: u32 = 8;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+pub fn handle(wait: bool) {
+    let day = if wait {
+        wait_for_unlock()
+    } else {
+        match Day::today() {
+            Some(day) => day,
+            None => {
+                eprintln!(
+                    "`today` command can only be run on the days of the event in \
+                    december. Please use `scaffold` with a specific day, \
+                    or `--wait` for the next puzzle."
+                );
+                process::exit(1)
+            }
+        }
+    };
+
+    scaffold::handle(day, false, None, &[], false);
+
+    if wait {
+        download_with_retry(day);
+    } else {
+        download::handle(day);
+    }
+
+    read::handle(day);
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn get_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs().try_into().unwrap_or(i64::MAX))
+}
+
+/// Returns the configured `AOC_YEAR`, or the current year on the server.
+fn get_year() -> i32 {
+    aoc_cli::get_year().map_or_else(
+        || {
+            let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).unwrap();
+            Utc::now().with_timezone(&offset).year()
+        },
+        i32::from,
+    )
+}
+
+/// Returns the next day to unlock after `now`, with its unlock timestamp.
+fn get_next_unlock(year: i32, now: i64) -> Option<(Day, i64)> {
+    all_days()
+        .map(|day| (day, day.unlock_timestamp(year)))
+        .find(|(_, unlock)| *unlock > now)
+}
+
+/// Shows a countdown until the next puzzle unlocks and returns its day.
+fn wait_for_unlock() -> Day {
+    let year = get_year();
+
+    let Some((day, unlock)) = get_next_unlock(year, get_now()) else {
+        eprintln!("There are no more puzzles to unlock in {year}.");
+        process::exit(1);
+    };
+
+    loop {
+        let remaining = unlock - get_now();
+        if remaining <= 0 {
+            break;
+        }
+
+        print!(
+            "\r⏳ Day {day} of {year} unlocks in {}   ",
+            format_countdown(remaining)
+        );
+        let _ = stdout().flush();
+        thread::sleep(Duration::from_secs(1));
+    }
+
+    println!("\r🔓 Day {day} of {year} unlocked!                    ");
+    day
+}
+
+fn get_backoff(attempt: u32) -> Duration {
+    INITIAL_BACKOFF
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(MAX_BACKOFF)
+}
+
+fn download_with_retry(day: Day) {
+    if aoc_cli::check().is_err() {
+        eprintln!(
+            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+        );
+        process::exit(1);
+    }
+
+    for attempt in 0..MAX_ATTEMPTS {
+        match aoc_cli::download(day) {
+            Ok(_) => return,
+            Err(e) if attempt + 1 < MAX_ATTEMPTS => {
+                let backoff = get_backoff(attempt);
+                eprintln!(
+                    "failed to download puzzle ({e}), retrying in {}s...",
+                    backoff.as_secs()
+                );
+                thread::sleep(backoff);
+            }
+            Err(e) => {
+                eprintln!("failed to call aoc-cli: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_countdown, get_backoff, get_next_unlock};
+    use crate::{day, template::Day};
+
+    #[test]
+    fn gets_next_unlock() {
+        let unlock = day!(1).unlock_timestamp(2025);
+        assert_eq!(get_next_unlock(2025, unlock - 1), Some((day!(1), unlock)));
+        assert_eq!(
+            get_next_unlock(2025, unlock).map(|(day, _)| day),
+            Some(day!(2))
+        );
+        assert_eq!(get_next_unlock(2025, i64::MAX), None::<(Day, i64)>);
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(59), "00:00:59");
+        assert_eq!(format_countdown(3723), "01:02:03");
+        assert_eq!(format_countdown(90_061), "1d 01:01:01");
+    }
+
+    #[test]
+    fn backs_off_exponentially() {
+        assert_eq!(get_backoff(0), Duration::from_secs(2));
+        assert_eq!(get_backoff(2), Duration::from_secs(8));
+        assert_eq!(get_backoff(10), Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,302 @@
+/// Re-runs a solution and its example tests whenever its sources or data files change.
+/// Changes are detected by polling modification times, so no external tools are needed.
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, Input, data_dir};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Bursts of saves (e.g. formatters writing after the editor) are coalesced until
+/// the watched files have been quiet for this long.
+const DEBOUNCE: Duration = Duration::from_millis(400);
+
+type Snapshot = HashMap<PathBuf, SystemTime>;
+
+pub fn handle(day: Day, release: bool, part: Option<u8>, input: &Input) {
+    let mut answers: HashMap<u8, String> = HashMap::new();
+    let mut snapshot = get_snapshot(day);
+
+    println!(
+        "👀 Watching {} file(s) for day {day}. Press Ctrl-C to stop.",
+        snapshot.len()
+    );
+
+    loop {
+        println!();
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        match run_solution(day, release, part, input) {
+            Ok(output) => {
+                let new_answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+                print_diff(&answers, &new_answers);
+                answers = new_answers;
+            }
+            Err(e) => eprintln!("Failed to run solution: {e}"),
+        }
+
+        match run_tests(day) {
+            Ok(output) => print_test_summary(&output),
+            Err(e) => eprintln!("Failed to run example tests: {e}"),
+        }
+
+        println!();
+        println!("{ANSI_ITALIC}Waiting for changes...{ANSI_RESET}");
+        snapshot = wait_for_changes(day, snapshot);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns the files that influence the result of `day`: its module, the library
+/// sources and its data files.
+fn get_watched_paths(day: Day) -> Vec<PathBuf> {
+    let src_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let bin_dir = src_dir.join("bin");
+    let mut paths = vec![bin_dir.join(format!("{day}.rs"))];
+
+    collect_sources(&src_dir, &bin_dir, &mut paths);
+
+    let prefix = day.to_string();
+    for folder in ["inputs", "examples"] {
+        let Ok(entries) = fs::read_dir(data_dir().join(folder)) else {
+            continue;
+        };
+
+        paths.extend(
+            entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(&prefix) && name.contains(".txt"))
+                }),
+        );
+    }
+
+    paths
+}
+
+/// Recursively collects `.rs` files below `dir`, skipping the solution bins.
+fn collect_sources(dir: &Path, bin_dir: &Path, paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(|entry| Some(entry.ok()?.path())) {
+        if path.is_dir() {
+            if path != bin_dir {
+                collect_sources(&path, bin_dir, paths);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            paths.push(path);
+        }
+    }
+}
+
+fn get_snapshot(day: Day) -> Snapshot {
+    get_watched_paths(day)
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).ok()?.modified().ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/// Blocks until the watched files changed and then stayed unchanged for [`DEBOUNCE`].
+fn wait_for_changes(day: Day, previous: Snapshot) -> Snapshot {
+    let mut current = previous.clone();
+
+    while current == previous {
+        thread::sleep(POLL_INTERVAL);
+        current = get_snapshot(day);
+    }
+
+    loop {
+        thread::sleep(DEBOUNCE);
+        let next = get_snapshot(day);
+        if next == current {
+            return current;
+        }
+        current = next;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn run_solution(
+    day: Day,
+    release: bool,
+    part: Option<u8>,
+    input: &Input,
+) -> Result<Output, std::io::Error> {
+    let mut args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+    ];
+
+    if release {
+        args.push("--release".to_string());
+    }
+
+    #[cfg(feature = "encrypted-inputs")]
+    args.extend(["--features".to_string(), "encrypted-inputs".to_string()]);
+
+    args.push("--".to_string());
+
+    if let Some(part) = part {
+        args.push("--part".to_string());
+        args.push(part.to_string());
+    }
+
+    args.extend(input.to_args());
+
+    // build errors and panics are forwarded, the answers are parsed from stdout.
+    let output = Command::new("cargo")
+        .args(&args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    Ok(output)
+}
+
+fn run_tests(day: Day) -> Result<Output, std::io::Error> {
+    Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &day.to_string()])
+        .stdin(Stdio::null())
+        .output()
+}
+
+fn print_test_summary(output: &Output) {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    if output.status.success() {
+        let summary = stdout
+            .lines()
+            .find(|line| line.starts_with("test result:"))
+            .unwrap_or("test result: ok.");
+        println!(
+            "Examples: ✔ {}",
+            summary.trim_start_matches("test result: ")
+        );
+    } else {
+        println!("Examples: ✖");
+        print!("{stdout}");
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip the escape sequence up to and including its final letter.
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+
+    out
+}
+
+/// Extracts the answer of each part from the output of a solution bin.
+fn parse_answers(stdout: &str) -> HashMap<u8, String> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            // intermediate results are overwritten with a carriage return.
+            let line = strip_ansi(line.rsplit('\r').next()?);
+            let rest = line.strip_prefix("Part ")?;
+            let (part, rest) = rest.split_once(':')?;
+            let part = part.parse::<u8>().ok()?;
+
+            let answer = match rest.rfind(" (") {
+                Some(index) if rest.trim_end().ends_with(')') => &rest[..index],
+                _ => rest,
+            };
+
+            Some((part, answer.trim().to_string()))
+        })
+        .collect()
+}
+
+fn print_diff(previous: &HashMap<u8, String>, current: &HashMap<u8, String>) {
+    if previous.is_empty() {
+        return;
+    }
+
+    println!();
+    for part in [1, 2] {
+        let line = match (previous.get(&part), current.get(&part)) {
+            (Some(old), Some(new)) if old == new => format!("unchanged ({new})"),
+            (Some(old), Some(new)) => format!("{old} → {ANSI_BOLD}{new}{ANSI_RESET}"),
+            (None, Some(new)) => format!("→ {ANSI_BOLD}{new}{ANSI_RESET}"),
+            (Some(old), None) => format!("{old} → ✖"),
+            (None, None) => continue,
+        };
+        println!("Part {part} {ANSI_ITALIC}diff{ANSI_RESET}: {line}");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{get_watched_paths, parse_answers, strip_ansi};
+    use crate::day;
+
+    #[test]
+    fn watches_sources_from_the_crate_root() {
+        let src_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let paths = get_watched_paths(day!(1));
+        assert!(paths.contains(&src_dir.join("bin").join("01.rs")));
+        assert!(paths.contains(&src_dir.join("lib.rs")));
+        assert!(!paths.contains(&src_dir.join("bin").join("02.rs")));
+    }
+
+    #[test]
+    fn strips_ansi_codes() {
+        assert_eq!(
+            strip_ansi("Part 1: \x1b[1m42\x1b[0m (1.0ms)"),
+            "Part 1: 42 (1.0ms)"
+        );
+    }
+
+    #[test]
+    fn parses_answers() {
+        let answers = parse_answers(
+            "Part 1: \x1b[1m3\x1b[0m\rPart 1: \x1b[1m3\x1b[0m (15.5µs)\nPart 2: \x1b[1m6\x1b[0m (4.3µs @ 10 samples)\n",
+        );
+        assert_eq!(answers.get(&1).map(String::as_str), Some("3"));
+        assert_eq!(answers.get(&2).map(String::as_str), Some("6"));
+    }
+
+    #[test]
+    fn parses_missing_answers() {
+        let answers = parse_answers("Part 1: ✖\rPart 1: ✖             \n");
+        assert_eq!(answers.get(&1).map(String::as_str), Some("✖"));
+    }
+}
@@ -0,0 +1,388 @@
+/// Module that stores inputs and puzzles encrypted at rest, so they can be committed
+/// to the repository without publishing them.
+///
+/// Encrypted files live next to their plaintext path with an `.enc` suffix and are sealed
+/// with ChaCha20-Poly1305. The key is derived from the `AOC_INPUT_KEY` env var with the
+/// memory-hard Argon2id, salted per key, so weak passphrases can't be brute-forced cheaply.
+/// The salt and cost parameters are stored in the header of each file.
+use std::{
+    ffi::OsString,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    AeadCore, ChaCha20Poly1305, KeyInit, Nonce,
+    aead::{Aead, OsRng, rand_core::RngCore},
+};
+
+pub const KEY_ENV_VAR: &str = "AOC_INPUT_KEY";
+pub const NEW_KEY_ENV_VAR: &str = "AOC_INPUT_NEW_KEY";
+
+/// Identifies (and versions) the format of an encrypted file. It's followed by the header:
+/// the salt, the Argon2id memory cost (in KiB), time cost and parallelism as little endian
+/// `u32`s, and the nonce.
+const MAGIC: &[u8] = b"AOCENC2\n";
+const SALT_LEN: usize = 16;
+const HEADER_LEN: usize = MAGIC.len() + SALT_LEN + 3 * 4 + NONCE_LEN;
+const NONCE_LEN: usize = 12;
+
+/// Files asking for more memory than this (in KiB) to derive their key are rejected.
+const MAX_MEMORY_COST: u32 = 1 << 20;
+
+#[derive(Debug)]
+pub enum CryptoError {
+    MissingKey(&'static str),
+    Malformed,
+    Decryption,
+    IO(io::Error),
+}
+
+impl Display for CryptoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptoError::MissingKey(var) => write!(f, "env var {var} is not set."),
+            CryptoError::Malformed => write!(f, "file is not an encrypted input."),
+            CryptoError::Decryption => {
+                write!(
+                    f,
+                    "could not decrypt file, the key is wrong or it was tampered with."
+                )
+            }
+            CryptoError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for CryptoError {}
+
+impl From<io::Error> for CryptoError {
+    fn from(e: io::Error) -> Self {
+        CryptoError::IO(e)
+    }
+}
+
+/// A cipher keyed from a passphrase. Files are encrypted with a key derived from a salt
+/// chosen once per `Key`, files sealed with other salts are decrypted by deriving theirs.
+pub struct Key {
+    passphrase: String,
+    salt: [u8; SALT_LEN],
+    params: Params,
+    cipher: ChaCha20Poly1305,
+}
+
+impl Key {
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        Self::with_params(passphrase, Params::DEFAULT)
+    }
+
+    /// Uses the given Argon2id cost parameters for files encrypted with this key.
+    pub fn with_params(passphrase: &str, params: Params) -> Self {
+        let mut salt = [0; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let cipher = derive_cipher(passphrase, &salt, params.clone())
+            .expect("valid parameters always derive a key");
+
+        Self {
+            passphrase: passphrase.to_string(),
+            salt,
+            params,
+            cipher,
+        }
+    }
+
+    /// Reads the passphrase from the env var `var`.
+    pub fn from_env(var: &'static str) -> Result<Self, CryptoError> {
+        match std::env::var(var) {
+            Ok(passphrase) if !passphrase.is_empty() => Ok(Self::from_passphrase(&passphrase)),
+            _ => Err(CryptoError::MissingKey(var)),
+        }
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext)
+            .expect("encrypting an in-memory buffer cannot fail");
+
+        let mut sealed = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+        sealed.extend_from_slice(MAGIC);
+        sealed.extend_from_slice(&self.salt);
+        for cost in [
+            self.params.m_cost(),
+            self.params.t_cost(),
+            self.params.p_cost(),
+        ] {
+            sealed.extend_from_slice(&cost.to_le_bytes());
+        }
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        sealed
+    }
+
+    pub fn decrypt(&self, sealed: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let body = sealed.strip_prefix(MAGIC).ok_or(CryptoError::Malformed)?;
+        if body.len() < HEADER_LEN - MAGIC.len() {
+            return Err(CryptoError::Malformed);
+        }
+
+        let (salt, body) = body.split_at(SALT_LEN);
+        let (costs, body) = body.split_at(3 * 4);
+        let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+
+        let [m_cost, t_cost, p_cost] = [0, 1, 2]
+            .map(|i| u32::from_le_bytes(costs[i * 4..i * 4 + 4].try_into().expect("4 bytes")));
+        if m_cost > MAX_MEMORY_COST {
+            return Err(CryptoError::Malformed);
+        }
+        let params =
+            Params::new(m_cost, t_cost, p_cost, None).map_err(|_| CryptoError::Malformed)?;
+
+        let derived;
+        let cipher = if salt == self.salt && params == self.params {
+            &self.cipher
+        } else {
+            derived = derive_cipher(&self.passphrase, salt, params)?;
+            &derived
+        };
+
+        cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CryptoError::Decryption)
+    }
+}
+
+fn derive_cipher(
+    passphrase: &str,
+    salt: &[u8],
+    params: Params,
+) -> Result<ChaCha20Poly1305, CryptoError> {
+    let mut key = [0; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|_| CryptoError::Malformed)?;
+    Ok(ChaCha20Poly1305::new(&key.into()))
+}
+
+/// Returns the path of the encrypted counterpart of `path`, e.g. `01.txt.enc` for `01.txt`.
+#[must_use]
+pub fn get_sealed_path(path: &Path) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".enc");
+    PathBuf::from(name)
+}
+
+/// Reads `path`, transparently decrypting its `.enc` counterpart if it exists.
+pub fn read_to_string(path: &Path) -> Result<String, CryptoError> {
+    let sealed_path = get_sealed_path(path);
+
+    if !sealed_path.exists() {
+        return Ok(fs::read_to_string(path)?);
+    }
+
+    let key = Key::from_env(KEY_ENV_VAR)?;
+    let plaintext = key.decrypt(&fs::read(sealed_path)?)?;
+    String::from_utf8(plaintext).map_err(|_| CryptoError::Malformed)
+}
+
+/// Encrypts the plaintext file at `path` to its `.enc` counterpart and removes the plaintext.
+pub fn seal_file(path: &Path) -> Result<PathBuf, CryptoError> {
+    let key = Key::from_env(KEY_ENV_VAR)?;
+    let sealed_path = get_sealed_path(path);
+    fs::write(&sealed_path, key.encrypt(&fs::read(path)?))?;
+    fs::remove_file(path)?;
+    Ok(sealed_path)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Re-encrypts the files at `sealed_paths` with a new key, all or none of them: every
+/// file is re-encrypted to a temporary file first, and the originals are only removed
+/// once all of them were replaced. Returns the file that failed otherwise.
+pub fn rekey_files(
+    sealed_paths: &[PathBuf],
+    old: &Key,
+    new: &Key,
+) -> Result<(), (PathBuf, CryptoError)> {
+    let tmp_paths: Vec<PathBuf> = sealed_paths
+        .iter()
+        .map(|path| with_suffix(path, ".tmp"))
+        .collect();
+    let remove_all = |paths: &[PathBuf]| {
+        for path in paths {
+            let _ = fs::remove_file(path);
+        }
+    };
+
+    for (i, (path, tmp_path)) in sealed_paths.iter().zip(&tmp_paths).enumerate() {
+        let result = fs::read(path)
+            .map_err(CryptoError::from)
+            .and_then(|sealed| old.decrypt(&sealed))
+            .and_then(|plaintext| Ok(fs::write(tmp_path, new.encrypt(&plaintext))?));
+
+        if let Err(e) = result {
+            remove_all(&tmp_paths[..i]);
+            return Err((path.clone(), e));
+        }
+    }
+
+    // Each original is moved aside before its replacement is moved in, so that all of
+    // them can be restored if any rename fails.
+    let old_paths: Vec<PathBuf> = sealed_paths
+        .iter()
+        .map(|path| with_suffix(path, ".old"))
+        .collect();
+    let mut moved = 0;
+    let mut failure = None;
+
+    for (i, path) in sealed_paths.iter().enumerate() {
+        if let Err(e) = fs::rename(path, &old_paths[i]) {
+            failure = Some((path.clone(), e));
+            break;
+        }
+        moved += 1;
+        if let Err(e) = fs::rename(&tmp_paths[i], path) {
+            failure = Some((path.clone(), e));
+            break;
+        }
+    }
+
+    if let Some((path, e)) = failure {
+        for (old_path, path) in old_paths.iter().zip(sealed_paths).take(moved) {
+            let _ = fs::rename(old_path, path);
+        }
+        remove_all(&tmp_paths);
+        return Err((path, e.into()));
+    }
+
+    remove_all(&old_paths);
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::fs;
+
+    use argon2::Params;
+
+    use super::{CryptoError, Key, MAGIC, rekey_files};
+
+    /// Keeps the key derivation cheap, the file header records the parameters.
+    fn get_key(passphrase: &str) -> Key {
+        Key::with_params(passphrase, Params::new(8, 1, 1, None).unwrap())
+    }
+
+    #[test]
+    fn roundtrips_plaintext() {
+        let key = get_key("hunter2");
+        let sealed = key.encrypt(b"1,2,3\n");
+        assert!(sealed.starts_with(MAGIC));
+        assert_eq!(key.decrypt(&sealed).unwrap(), b"1,2,3\n");
+    }
+
+    #[test]
+    fn decrypts_with_other_salts_and_params() {
+        let sealed = get_key("hunter2").encrypt(b"1,2,3\n");
+        let other = Key::with_params("hunter2", Params::new(16, 2, 1, None).unwrap());
+        assert_eq!(other.decrypt(&sealed).unwrap(), b"1,2,3\n");
+        assert_ne!(
+            sealed[MAGIC.len()..],
+            other.encrypt(b"1,2,3\n")[MAGIC.len()..]
+        );
+    }
+
+    #[test]
+    fn rejects_excessive_memory_costs() {
+        let mut sealed = get_key("hunter2").encrypt(b"1,2,3\n");
+        let offset = MAGIC.len() + 16;
+        sealed[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            get_key("hunter2").decrypt(&sealed),
+            Err(CryptoError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn uses_unique_nonces() {
+        let key = get_key("hunter2");
+        assert_ne!(key.encrypt(b"1,2,3\n"), key.encrypt(b"1,2,3\n"));
+    }
+
+    #[test]
+    fn rejects_wrong_key() {
+        let sealed = get_key("hunter2").encrypt(b"1,2,3\n");
+        let result = get_key("hunter3").decrypt(&sealed);
+        assert!(matches!(result, Err(CryptoError::Decryption)));
+    }
+
+    #[test]
+    fn rejects_tampered_files() {
+        let key = get_key("hunter2");
+        let mut sealed = key.encrypt(b"1,2,3\n");
+        *sealed.last_mut().unwrap() ^= 1;
+        assert!(matches!(key.decrypt(&sealed), Err(CryptoError::Decryption)));
+    }
+
+    #[test]
+    fn rejects_plaintext_files() {
+        let key = get_key("hunter2");
+        assert!(matches!(
+            key.decrypt(b"1,2,3\n"),
+            Err(CryptoError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn rekeys_all_files_or_none() {
+        let dir = std::env::temp_dir().join(format!("aoc-rekey-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let (old, new) = (get_key("old"), get_key("new"));
+        let paths = [dir.join("01.txt.enc"), dir.join("02.txt.enc")];
+        fs::write(&paths[0], old.encrypt(b"1")).unwrap();
+        fs::write(&paths[1], b"not sealed").unwrap();
+
+        let (failed, _) = rekey_files(&paths, &old, &new).unwrap_err();
+        assert_eq!(failed, paths[1]);
+        assert_eq!(old.decrypt(&fs::read(&paths[0]).unwrap()).unwrap(), b"1");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        fs::write(&paths[1], old.encrypt(b"2")).unwrap();
+        rekey_files(&paths, &old, &new).unwrap();
+        assert_eq!(new.decrypt(&fs::read(&paths[0]).unwrap()).unwrap(), b"1");
+        assert_eq!(new.decrypt(&fs::read(&paths[1]).unwrap()).unwrap(), b"2");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn restores_all_files_if_a_rename_fails() {
+        let dir = std::env::temp_dir().join(format!("aoc-rekey-rename-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let (old, new) = (get_key("old"), get_key("new"));
+        let paths = [dir.join("01.txt.enc"), dir.join("02.txt.enc")];
+        fs::write(&paths[0], old.encrypt(b"1")).unwrap();
+        fs::write(&paths[1], old.encrypt(b"2")).unwrap();
+
+        // A non-empty folder in the way makes moving the second original aside fail,
+        // after the first file was already replaced.
+        fs::create_dir_all(dir.join("02.txt.enc.old").join("blocker")).unwrap();
+
+        let (failed, _) = rekey_files(&paths, &old, &new).unwrap_err();
+        assert_eq!(failed, paths[1]);
+        assert_eq!(old.decrypt(&fs::read(&paths[0]).unwrap()).unwrap(), b"1");
+        assert_eq!(old.decrypt(&fs::read(&paths[1]).unwrap()).unwrap(), b"2");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 3);
+    }
+}
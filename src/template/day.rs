@@ -5,8 +5,13 @@ use std::str::FromStr;
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
-#[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+use crate::template::aoc_cli;
+
+/// Env var that overrides the number of puzzles in the event.
+const DAY_COUNT_ENV_VAR: &str = "AOC_DAYS";
+
+/// The UTC offset in hours of the Advent of Code server, puzzles unlock at midnight in this timezone.
+pub const SERVER_UTC_OFFSET: i32 = -5;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
@@ -35,15 +40,37 @@ impl Day {
     pub fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// Returns the unix timestamp at which the puzzle of this day unlocks in `year`.
+    pub fn unlock_timestamp(self, year: i32) -> i64 {
+        let days = days_from_civil(year, 12, u32::from(self.0));
+        days * 86_400 - i64::from(SERVER_UTC_OFFSET) * 3600
+    }
+
+    /// Returns `true` if the day is part of the event, see [`get_day_count`].
+    pub fn is_in_event(self) -> bool {
+        self.0 <= get_day_count()
+    }
+}
+
+/// Returns the number of days since the unix epoch for a date of the proleptic gregorian calendar.
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = i64::from(if month <= 2 { year - 1 } else { year });
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
 }
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's a day of the event in december, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        if today.month() == 12 && today.day() <= u32::from(get_day_count()) {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
@@ -51,6 +78,27 @@ impl Day {
     }
 }
 
+/// Returns the number of puzzles in an event: 25 up to 2024, 12 since 2025.
+pub fn get_day_count_for_year(year: i32) -> u8 {
+    if year >= 2025 { 12 } else { 25 }
+}
+
+/// Returns the number of puzzles in the configured event.
+///
+/// Can be overridden with the `AOC_DAYS` env var, otherwise it's derived from `AOC_YEAR`.
+/// Defaults to 25 if neither is set.
+pub fn get_day_count() -> u8 {
+    if let Some(count) = std::env::var(DAY_COUNT_ENV_VAR)
+        .ok()
+        .and_then(|count| count.parse::<u8>().ok())
+        .filter(|count| (1..=25).contains(count))
+    {
+        return count;
+    }
+
+    aoc_cli::get_year().map_or(25, |year| get_day_count_for_year(i32::from(year)))
+}
+
 impl Display for Day {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:02}", self.0)
@@ -94,20 +142,29 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the event, see [`get_day_count`].
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the event, from the 1st to the last day.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::with_last(get_day_count())
+    }
+
+    /// Yields the days from the 1st to `last`, which is clamped to 25.
+    pub fn with_last(last: u8) -> Self {
+        Self {
+            current: 1,
+            last: last.min(25),
+        }
     }
 }
 
@@ -115,7 +172,7 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
         // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
@@ -143,11 +200,19 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{Day, all_days};
+    use super::{AllDays, Day, get_day_count_for_year};
+
+    #[test]
+    fn unlock_timestamp() {
+        // 2025-12-01T05:00:00Z
+        assert_eq!(Day(1).unlock_timestamp(2025), 1_764_565_200);
+        // 2024-12-25T05:00:00Z
+        assert_eq!(Day(25).unlock_timestamp(2024), 1_735_102_800);
+    }
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = AllDays::with_last(25);
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -176,6 +241,19 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_iterator_for_short_events() {
+        assert_eq!(AllDays::with_last(12).last(), Some(Day(12)));
+        assert_eq!(AllDays::with_last(12).count(), 12);
+        assert_eq!(AllDays::with_last(30).count(), 25);
+    }
+
+    #[test]
+    fn day_count_for_year() {
+        assert_eq!(get_day_count_for_year(2024), 25);
+        assert_eq!(get_day_count_for_year(2025), 12);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
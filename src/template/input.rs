@@ -0,0 +1,200 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, Read},
+};
+
+use crate::template::{Day, try_read_data_file};
+
+/// Where a solution binary reads its puzzle input from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Input {
+    /// The downloaded input at `data/inputs/DD.txt`.
+    Puzzle,
+    /// An example at `data/examples/DD.txt`, or `data/examples/DD-N.txt` if a number is given.
+    Example(Option<u8>),
+    /// An arbitrary file.
+    Path(String),
+    /// Standard input, passed as `--input -`.
+    Stdin,
+}
+
+impl Input {
+    /// Parses the `--input <path>`, `--input -` and `--example [N]` flags of a solution binary.
+    pub fn from_args(args: &[String]) -> Result<Self, InputArgsError> {
+        Self::take_from_args(&mut args.to_vec())
+    }
+
+    /// Like [`Input::from_args`], but removes the flags and their values from `args`,
+    /// so that `cargo solve` can parse the remaining arguments itself.
+    pub fn take_from_args(args: &mut Vec<String>) -> Result<Self, InputArgsError> {
+        let input = args.iter().position(|x| x == "--input");
+        let example = args.iter().position(|x| x == "--example");
+
+        match (input, example) {
+            (Some(_), Some(_)) => Err(InputArgsError::Conflict),
+            (Some(i), None) => {
+                let input = match args.get(i + 1).map(String::as_str) {
+                    Some("-") => Input::Stdin,
+                    Some(path) if !path.starts_with("--") => Input::Path(path.to_string()),
+                    _ => return Err(InputArgsError::MissingPath),
+                };
+                args.drain(i..=i + 1);
+                Ok(input)
+            }
+            (None, Some(i)) => {
+                let n = args.get(i + 1).and_then(|n| n.parse::<u8>().ok());
+                args.drain(i..=i + usize::from(n.is_some()));
+                Ok(Input::Example(n))
+            }
+            (None, None) => Ok(Input::Puzzle),
+        }
+    }
+
+    /// Converts the input back into the command-line flags that select it.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Input::Puzzle => vec![],
+            Input::Example(None) => vec!["--example".into()],
+            Input::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+            Input::Path(path) => vec!["--input".into(), path.clone()],
+            Input::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// Reads the input for `day`.
+    pub fn read(&self, day: Day) -> Result<String, io::Error> {
+        match self {
+            Input::Puzzle => try_read_data_file("inputs", &format!("{day}.txt")),
+            Input::Example(None) => try_read_data_file("examples", &format!("{day}.txt")),
+            Input::Example(Some(n)) => try_read_data_file("examples", &format!("{day}-{n}.txt")),
+            Input::Path(path) => fs::read_to_string(path),
+            Input::Stdin => {
+                let mut buf = String::new();
+                io::stdin().read_to_string(&mut buf)?;
+                Ok(buf)
+            }
+        }
+    }
+}
+
+/// An error which can be returned when parsing the input flags of a solution binary.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InputArgsError {
+    MissingPath,
+    Conflict,
+}
+
+impl std::error::Error for InputArgsError {}
+
+impl Display for InputArgsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputArgsError::MissingPath => {
+                f.write_str("expecting a path or `-` for stdin after `--input`")
+            }
+            InputArgsError::Conflict => f.write_str("`--input` and `--example` are exclusive"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Input, InputArgsError};
+    use crate::day;
+
+    fn parse(args: &[&str]) -> Result<Input, InputArgsError> {
+        let args: Vec<String> = args.iter().map(|s| (*s).to_string()).collect();
+        Input::from_args(&args)
+    }
+
+    #[test]
+    fn defaults_to_puzzle_input() {
+        assert_eq!(parse(&["01", "--time"]), Ok(Input::Puzzle));
+    }
+
+    #[test]
+    fn parses_input_path() {
+        assert_eq!(
+            parse(&["01", "--input", "other.txt"]),
+            Ok(Input::Path("other.txt".into()))
+        );
+    }
+
+    #[test]
+    fn parses_stdin() {
+        assert_eq!(parse(&["01", "--input", "-"]), Ok(Input::Stdin));
+    }
+
+    #[test]
+    fn parses_examples() {
+        assert_eq!(parse(&["01", "--example"]), Ok(Input::Example(None)));
+        assert_eq!(
+            parse(&["01", "--example", "2"]),
+            Ok(Input::Example(Some(2)))
+        );
+        assert_eq!(
+            parse(&["01", "--example", "--time"]),
+            Ok(Input::Example(None))
+        );
+    }
+
+    #[test]
+    fn rejects_missing_path() {
+        assert_eq!(parse(&["01", "--input"]), Err(InputArgsError::MissingPath));
+        assert_eq!(
+            parse(&["01", "--input", "--time"]),
+            Err(InputArgsError::MissingPath)
+        );
+    }
+
+    #[test]
+    fn rejects_conflicting_flags() {
+        assert_eq!(
+            parse(&["01", "--input", "-", "--example"]),
+            Err(InputArgsError::Conflict)
+        );
+    }
+
+    #[test]
+    fn takes_flags_from_args() {
+        let mut args: Vec<String> = ["--release", "--example", "2", "--watch"]
+            .iter()
+            .map(|s| (*s).to_string())
+            .collect();
+        assert_eq!(
+            Input::take_from_args(&mut args),
+            Ok(Input::Example(Some(2)))
+        );
+        assert_eq!(args, ["--release", "--watch"]);
+
+        let mut args: Vec<String> = ["--input", "-", "--time"]
+            .iter()
+            .map(|s| (*s).to_string())
+            .collect();
+        assert_eq!(Input::take_from_args(&mut args), Ok(Input::Stdin));
+        assert_eq!(args, ["--time"]);
+    }
+
+    #[test]
+    fn fails_to_read_missing_files() {
+        let error = Input::Example(Some(99)).read(day!(25)).unwrap_err();
+        assert!(error.to_string().contains("25-99.txt"));
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for input in [
+            Input::Puzzle,
+            Input::Example(None),
+            Input::Example(Some(2)),
+            Input::Path("other.txt".into()),
+            Input::Stdin,
+        ] {
+            let args: Vec<String> = input.to_args();
+            assert_eq!(Input::from_args(&args), Ok(input));
+        }
+    }
+}
@@ -0,0 +1,506 @@
+/// Module that imports a private leaderboard and renders it as Markdown tables.
+/// The leaderboard is stored as the JSON document served by Advent of Code.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, all_days, aoc_cli, data_dir, format_countdown, readme_benchmarks};
+
+static LEADERBOARD_FILE_NAME: &str = "leaderboard.json";
+pub static MARKER: &str = "<!--- leaderboard table --->";
+
+/// Env var that holds the session cookie, shared with aoc-cli.
+static SESSION_ENV_VAR: &str = "ADVENT_OF_CODE_SESSION";
+/// File that holds the session cookie, shared with aoc-cli.
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+
+fn get_leaderboard_path() -> PathBuf {
+    data_dir().join(LEADERBOARD_FILE_NAME)
+}
+
+#[derive(Debug)]
+pub enum LeaderboardError {
+    Parser(String),
+    MissingSession,
+    Download(String),
+    IO(io::Error),
+}
+
+impl Display for LeaderboardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LeaderboardError::Parser(e) => write!(f, "invalid leaderboard: {e}"),
+            LeaderboardError::MissingSession => write!(
+                f,
+                "no session cookie found in {SESSION_ENV_VAR} or ~/{SESSION_FILE_NAME}."
+            ),
+            LeaderboardError::Download(e) => write!(f, "could not download leaderboard: {e}"),
+            LeaderboardError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for LeaderboardError {}
+
+impl From<io::Error> for LeaderboardError {
+    fn from(e: io::Error) -> Self {
+        LeaderboardError::IO(e)
+    }
+}
+
+impl From<readme_benchmarks::Error> for LeaderboardError {
+    fn from(e: readme_benchmarks::Error) -> Self {
+        match e {
+            readme_benchmarks::Error::Parser(e) => LeaderboardError::Parser(e),
+            readme_benchmarks::Error::IO(e) => LeaderboardError::IO(e),
+        }
+    }
+}
+
+/// A member of a private leaderboard.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Member {
+    pub id: u64,
+    pub name: String,
+    pub local_score: u64,
+    /// Unix timestamps at which the stars of a day were earned.
+    pub stars: HashMap<u8, [Option<i64>; 2]>,
+}
+
+impl Member {
+    pub fn get_star(&self, day: Day, part: usize) -> Option<i64> {
+        self.stars.get(&day.into_inner())?[part - 1]
+    }
+
+    /// Returns the seconds between earning the first and the second star of a day.
+    pub fn get_delta(&self, day: Day) -> Option<i64> {
+        Some(self.get_star(day, 2)? - self.get_star(day, 1)?)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Leaderboard {
+    pub year: i32,
+    pub owner_id: u64,
+    /// Sorted by local score, best first.
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    /// Rehydrate the leaderboard from its JSON file.
+    pub fn read_from_file() -> Result<Self, LeaderboardError> {
+        let json = fs::read_to_string(get_leaderboard_path())?;
+        Leaderboard::try_from(json).map_err(LeaderboardError::Parser)
+    }
+
+    /// Validates a leaderboard document saved from the website and stores it.
+    pub fn import(path: &Path) -> Result<Self, LeaderboardError> {
+        store(fs::read_to_string(path)?)
+    }
+
+    /// Downloads a leaderboard with the session cookie used by aoc-cli and stores it.
+    pub fn download(id: &str) -> Result<Self, LeaderboardError> {
+        let session = get_session()?;
+        let year = aoc_cli::get_year().ok_or_else(|| {
+            LeaderboardError::Download("env var AOC_YEAR is not set or not a year.".into())
+        })?;
+
+        // The cookie is passed as config on stdin, arguments are visible to other users.
+        let mut child = Command::new("curl")
+            .args([
+                "--silent",
+                "--show-error",
+                "--fail",
+                "--config",
+                "-",
+                &format!("https://adventofcode.com/{year}/leaderboard/private/view/{id}.json"),
+            ])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|_| LeaderboardError::Download("curl could not be called.".into()))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            let session = session.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(stdin, "cookie = \"session={session}\"")?;
+        }
+        let output = child.wait_with_output()?;
+
+        if !output.status.success() {
+            return Err(LeaderboardError::Download(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        store(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    pub fn get_member(&self, name_or_id: &str) -> Option<&Member> {
+        self.members
+            .iter()
+            .find(|m| m.name == name_or_id || m.id.to_string() == name_or_id)
+    }
+}
+
+fn store(json: String) -> Result<Leaderboard, LeaderboardError> {
+    let leaderboard = Leaderboard::try_from(json.clone()).map_err(LeaderboardError::Parser)?;
+    fs::write(get_leaderboard_path(), json)?;
+    Ok(leaderboard)
+}
+
+fn get_session() -> Result<String, LeaderboardError> {
+    if let Ok(session) = env::var(SESSION_ENV_VAR) {
+        return Ok(session.trim().to_string());
+    }
+
+    let home = env::var_os("HOME").ok_or(LeaderboardError::MissingSession)?;
+    fs::read_to_string(Path::new(&home).join(SESSION_FILE_NAME))
+        .map(|session| session.trim().to_string())
+        .map_err(|_| LeaderboardError::MissingSession)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Escapes a member name for a Markdown table cell.
+fn escape_name(name: &str) -> String {
+    name.replace('|', "\\|")
+}
+
+fn construct_stars_table(leaderboard: &Leaderboard) -> Vec<String> {
+    let days: Vec<Day> = all_days().collect();
+
+    let mut lines = vec![
+        format!(
+            "| Member | Score | {} |",
+            days.iter()
+                .map(|d| d.into_inner().to_string())
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ),
+        format!("| :--- | :---: |{}", " :---: |".repeat(days.len())),
+    ];
+
+    for member in &leaderboard.members {
+        let stars: Vec<&str> = days
+            .iter()
+            .map(
+                |day| match (member.get_star(*day, 1), member.get_star(*day, 2)) {
+                    (Some(_), Some(_)) => "★",
+                    (Some(_), None) => "☆",
+                    _ => " ",
+                },
+            )
+            .collect();
+
+        lines.push(format!(
+            "| {} | {} | {} |",
+            escape_name(&member.name),
+            member.local_score,
+            stars.join(" | ")
+        ));
+    }
+
+    lines
+}
+
+fn construct_delta_table(leaderboard: &Leaderboard) -> Vec<String> {
+    let days: Vec<Day> = all_days().collect();
+
+    let mut lines = vec![
+        format!(
+            "| Member | {} |",
+            days.iter()
+                .map(|d| d.into_inner().to_string())
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ),
+        format!("| :--- |{}", " :---: |".repeat(days.len())),
+    ];
+
+    for member in &leaderboard.members {
+        let deltas: Vec<String> = days
+            .iter()
+            .map(|day| member.get_delta(*day).map_or("-".into(), format_countdown))
+            .collect();
+        lines.push(format!(
+            "| {} | {} |",
+            escape_name(&member.name),
+            deltas.join(" | ")
+        ));
+    }
+
+    lines
+}
+
+fn construct_personal_table(leaderboard: &Leaderboard, member: &Member) -> Vec<String> {
+    let mut lines = vec![
+        "| Day | Part 1 | Part 2 | Delta |".into(),
+        "| :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for day in all_days() {
+        if member.get_star(day, 1).is_none() {
+            continue;
+        }
+
+        let unlock = day.unlock_timestamp(leaderboard.year);
+        let time = |part| {
+            member
+                .get_star(day, part)
+                .map_or("-".into(), |ts| format_countdown(ts - unlock))
+        };
+
+        lines.push(format!(
+            "| {} | `{}` | `{}` | `{}` |",
+            day.into_inner(),
+            time(1),
+            time(2),
+            member.get_delta(day).map_or("-".into(), format_countdown)
+        ));
+    }
+
+    lines
+}
+
+/// Renders the leaderboard, wrapped in markers. Personal times are shown for `member`.
+#[must_use]
+pub fn construct_tables(leaderboard: &Leaderboard, member: Option<&Member>) -> String {
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("## {} Leaderboard", leaderboard.year),
+        String::new(),
+    ];
+
+    lines.extend(construct_stars_table(leaderboard));
+    lines.extend([
+        String::new(),
+        "### Time between parts".into(),
+        String::new(),
+    ]);
+    lines.extend(construct_delta_table(leaderboard));
+
+    if let Some(member) = member {
+        lines.extend([
+            String::new(),
+            format!("### Completion times of {}", member.name),
+            String::new(),
+        ]);
+        lines.extend(construct_personal_table(leaderboard, member));
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+pub fn update_readme(table: &str) -> Result<(), LeaderboardError> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    readme_benchmarks::replace_section(&mut readme, MARKER, table)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reads a number that is either a JSON number or a numeric string.
+fn get_number(value: &JsonValue) -> Option<f64> {
+    match value {
+        JsonValue::Number(n) => Some(*n),
+        JsonValue::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+impl TryFrom<String> for Leaderboard {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let year = json
+            .get("event")
+            .and_then(get_number)
+            .ok_or("expected `json.event` to be a year.")? as i32;
+
+        let owner_id = json
+            .get("owner_id")
+            .and_then(get_number)
+            .ok_or("expected `json.owner_id` to be a number.")? as u64;
+
+        let mut members = json
+            .get("members")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected `json.members` to be an object.")?
+            .values()
+            .map(Member::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        Ok(Leaderboard {
+            year,
+            owner_id,
+            members,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Member {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected member to be a JSON object.")?;
+
+        let id = json
+            .get("id")
+            .and_then(get_number)
+            .ok_or("Expected member.id to be a number.")? as u64;
+
+        // members that did not set a name are shown like on the website.
+        let name = json
+            .get("name")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .unwrap_or_else(|| format!("(anonymous user #{id})"));
+
+        let local_score = json
+            .get("local_score")
+            .and_then(get_number)
+            .ok_or("Expected member.local_score to be a number.")? as u64;
+
+        let days = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected member.completion_day_level to be an object.")?;
+
+        let mut stars = HashMap::new();
+
+        for (day, parts) in days {
+            let day: u8 = day
+                .parse()
+                .map_err(|_| "Expected completion_day_level keys to be days.")?;
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected completion_day_level values to be objects.")?;
+
+            let get_star_ts = |part: &str| -> Option<i64> {
+                parts
+                    .get(part)?
+                    .get::<HashMap<String, JsonValue>>()?
+                    .get("get_star_ts")
+                    .and_then(get_number)
+                    .map(|ts| ts as i64)
+            };
+
+            stars.insert(day, [get_star_ts("1"), get_star_ts("2")]);
+        }
+
+        Ok(Member {
+            id,
+            name,
+            local_score,
+            stars,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Leaderboard, MARKER, construct_tables};
+    use crate::day;
+
+    fn get_mock_leaderboard() -> Leaderboard {
+        Leaderboard::try_from(
+            r#"{
+                "event": "2025",
+                "owner_id": 1,
+                "members": {
+                    "1": {
+                        "id": 1, "name": "felix", "local_score": 10, "stars": 3,
+                        "completion_day_level": {
+                            "1": {
+                                "1": { "get_star_ts": 1764565800, "star_index": 1 },
+                                "2": { "get_star_ts": 1764566400, "star_index": 2 }
+                            },
+                            "2": { "1": { "get_star_ts": 1764655200, "star_index": 3 } }
+                        }
+                    },
+                    "2": {
+                        "id": 2, "name": null, "local_score": 4, "stars": 2,
+                        "completion_day_level": {
+                            "1": {
+                                "1": { "get_star_ts": "1764568800" },
+                                "2": { "get_star_ts": "1764655200" }
+                            }
+                        }
+                    }
+                }
+            }"#
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_leaderboard() {
+        let leaderboard = get_mock_leaderboard();
+        assert_eq!(leaderboard.year, 2025);
+        assert_eq!(leaderboard.members.len(), 2);
+        assert_eq!(leaderboard.members[0].name, "felix");
+        assert_eq!(leaderboard.members[1].name, "(anonymous user #2)");
+        assert_eq!(leaderboard.members[0].get_delta(day!(1)), Some(600));
+        assert_eq!(leaderboard.members[0].get_delta(day!(2)), None);
+        assert_eq!(leaderboard.members[1].get_delta(day!(1)), Some(86_400));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_leaderboards() {
+        Leaderboard::try_from(r#"{ "event": "2025" }"#.to_string()).unwrap();
+    }
+
+    #[test]
+    fn escapes_member_names() {
+        let mut leaderboard = get_mock_leaderboard();
+        leaderboard.members[0].name = "a | b".into();
+        let tables = construct_tables(&leaderboard, None);
+        assert!(tables.contains("| a \\| b | 10 | ★ | ☆ |"));
+        assert!(tables.contains("| a \\| b | 00:10:00 | - |"));
+    }
+
+    #[test]
+    fn constructs_tables() {
+        let leaderboard = get_mock_leaderboard();
+        let tables = construct_tables(&leaderboard, leaderboard.get_member("felix"));
+
+        assert!(tables.starts_with(MARKER) && tables.ends_with(MARKER));
+        assert!(tables.contains("| felix | 10 | ★ | ☆ |"));
+        assert!(tables.contains("| (anonymous user #2) | 1d 00:00:00 | - |"));
+        assert!(tables.contains("### Completion times of felix"));
+        assert!(tables.contains("| 1 | `00:10:00` | `00:20:00` | `00:10:00` |"));
+        assert!(tables.contains("| 2 | `01:00:00` | `-` | `-` |"));
+    }
+}
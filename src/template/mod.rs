@@ -1,57 +1,147 @@
-use std::{env, fs};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 
 pub mod aoc_cli;
 pub mod commands;
 pub mod runner;
 
 pub use day::*;
+pub use input::*;
+pub use scaling::Rng;
 
+mod answers;
+mod checksums;
+#[cfg(feature = "encrypted-inputs")]
+mod crypto;
 mod day;
+mod input;
+mod leaderboard;
 mod readme_benchmarks;
+mod registry;
 mod run_multi;
+mod scaling;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Returns the root directory of the data store.
+///
+/// Defaults to the `data` folder of this crate, which makes binaries independent of the
+/// working directory they are run from. Can be overridden with the `AOC_DATA_DIR` env var.
+#[must_use]
+pub fn data_dir() -> PathBuf {
+    env::var_os("AOC_DATA_DIR").map_or_else(
+        || Path::new(env!("CARGO_MANIFEST_DIR")).join("data"),
+        PathBuf::from,
+    )
+}
+
+/// Formats a duration in seconds, e.g. `01:02:03` or `2d 01:02:03`.
+fn format_countdown(seconds: i64) -> String {
+    let (days, rest) = (seconds / 86_400, seconds % 86_400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60
+    );
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_data_file(folder, &format!("{day}.txt"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_data_file(folder, &format!("{day}-{part}.txt"))
+}
+
+fn read_data_file(folder: &str, file_name: &str) -> String {
+    try_read_data_file(folder, file_name).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads a data file like [`read_file`], returning an error instead of panicking.
+fn try_read_data_file(folder: &str, file_name: &str) -> Result<String, std::io::Error> {
+    let filepath = data_dir().join(folder).join(file_name);
+    let contents = read_to_string(&filepath).map_err(|e| {
+        std::io::Error::other(format!(
+            "could not open input file \"{}\": {e}",
+            filepath.display()
+        ))
+    })?;
+
+    for warning in checksums::verify(&format!("{folder}/{file_name}"), &contents) {
+        eprintln!("Warning: \"{}\" {warning}", filepath.display());
+    }
+
+    Ok(contents)
+}
+
+#[cfg(feature = "encrypted-inputs")]
+fn read_to_string(path: &Path) -> Result<String, crypto::CryptoError> {
+    crypto::read_to_string(path)
+}
+
+#[cfg(not(feature = "encrypted-inputs"))]
+fn read_to_string(path: &Path) -> Result<String, std::io::Error> {
+    std::fs::read_to_string(path)
+}
+
+/// Returns the files that store the data file at `path`: the plaintext and, with encrypted
+/// inputs, its encrypted counterpart, as far as they exist.
+fn get_data_file_paths(path: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![path.to_path_buf()];
+    #[cfg(feature = "encrypted-inputs")]
+    paths.push(crypto::get_sealed_path(path));
+    paths.retain(|path| path.exists());
+    paths
+}
+
+/// Checks whether a data file exists, either as plaintext or as its encrypted counterpart.
+fn data_file_exists(path: &Path) -> bool {
+    !get_data_file_paths(path).is_empty()
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Days that declare an input generator with `generator = generate_input` (after the part,
+/// if one is given) can be run with `--scale`, which benchmarks them on generated inputs of
+/// increasing size.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, None, [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, None, [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, None, [part_two, 2]);
+    };
+    ($day:expr, generator = $generator:path) => {
+        $crate::solution!(@impl $day, Some($generator as $crate::template::runner::Generator), [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, generator = $generator:path) => {
+        $crate::solution!(@impl $day, Some($generator as $crate::template::runner::Generator), [part_one, 1]);
+    };
+    ($day:expr, 2, generator = $generator:path) => {
+        $crate::solution!(@impl $day, Some($generator as $crate::template::runner::Generator), [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $generator:expr, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -61,8 +151,23 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let selected_part = get_selected_part();
+            if std::env::args().any(|x| x == "--scale") {
+                let generator = require_generator(DAY, $generator);
+                $(
+                    if selected_part.is_none_or(|part| part == $part) {
+                        run_scaled($func, generator, DAY, $part);
+                    }
+                )*
+                return;
+            }
+
+            let input = read_input(DAY);
+            $(
+                if selected_part.is_none_or(|part| part == $part) {
+                    run_part($func, &input, DAY, $part);
+                }
+            )*
         }
     };
 }
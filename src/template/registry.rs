@@ -0,0 +1,278 @@
+/// Module that maintains an index of the scaffolded days.
+/// `scaffold` registers new days, solution runs record which parts produce a result.
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+/// The registry lives next to the solutions it indexes, independent of the working directory.
+fn get_registry_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("registry.json")
+}
+
+/// A single scaffolded day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub day: Day,
+    pub title: Option<String>,
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+/// The set of scaffolded days, ordered by day.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Registry {
+    pub days: Vec<Entry>,
+}
+
+impl Registry {
+    /// Dehydrate the registry to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_registry_path())?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the registry from a JSON file. If not present, returns an empty registry.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(get_registry_path())
+            .map_err(|x| x.to_string())
+            .and_then(Registry::try_from)
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, day: Day) -> Option<&Entry> {
+        self.days.iter().find(|e| e.day == day)
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.get(day).is_some()
+    }
+
+    /// Adds a day to the registry. The title of an existing entry is only replaced
+    /// if a new one is known, the implemented parts are kept.
+    pub fn register(&mut self, day: Day, title: Option<String>) {
+        match self.days.iter_mut().find(|e| e.day == day) {
+            Some(entry) => {
+                if title.is_some() {
+                    entry.title = title;
+                }
+            }
+            None => {
+                self.days.push(Entry {
+                    day,
+                    title,
+                    part_1: false,
+                    part_2: false,
+                });
+                self.days.sort_unstable_by_key(|e| e.day);
+            }
+        }
+    }
+
+    /// Records which parts of a registered day produce a result.
+    /// Returns `true` if the registry changed.
+    pub fn set_parts(&mut self, day: Day, part_1: bool, part_2: bool) -> bool {
+        match self.days.iter_mut().find(|e| e.day == day) {
+            Some(entry) if entry.part_1 != part_1 || entry.part_2 != part_2 => {
+                entry.part_1 = part_1;
+                entry.part_2 = part_2;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Registry> for JsonValue {
+    fn from(value: Registry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "days".into(),
+            JsonValue::Array(value.days.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Registry {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_days = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("days")
+            .ok_or("expected JSON document to have key `days`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.days` to be an array.")?;
+
+        let mut days = json_days
+            .iter()
+            .map(Entry::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        days.sort_unstable_by_key(|e| e.day);
+        Ok(Registry { days })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Entry> for JsonValue {
+    fn from(value: &Entry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        if let Some(title) = &value.title {
+            map.insert("title".into(), JsonValue::String(title.clone()));
+        }
+        map.insert("part_1".into(), JsonValue::Boolean(value.part_1));
+        map.insert("part_2".into(), JsonValue::Boolean(value.part_2));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Entry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected entry to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected entry.day to be a Day struct.")?;
+
+        // unknown titles are left out, older registries store them as null.
+        let title = match json.get("title") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected entry.title to be a string.")?,
+            ),
+        };
+
+        let part_1 = json
+            .get("part_1")
+            .and_then(|v| v.get::<bool>().copied())
+            .ok_or("Expected entry.part_1 to be a boolean.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .and_then(|v| v.get::<bool>().copied())
+            .ok_or("Expected entry.part_2 to be a boolean.")?;
+
+        Ok(Entry {
+            day,
+            title: title.cloned(),
+            part_1,
+            part_2,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Entry, Registry};
+    use crate::day;
+
+    fn get_mock_registry() -> Registry {
+        Registry {
+            days: vec![
+                Entry {
+                    day: day!(1),
+                    title: Some("Secret Entrance".into()),
+                    part_1: true,
+                    part_2: true,
+                },
+                Entry {
+                    day: day!(3),
+                    title: None,
+                    part_1: true,
+                    part_2: false,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let registry = get_mock_registry();
+        let json = tinyjson::JsonValue::from(registry.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Registry::try_from(json).unwrap(), registry);
+    }
+
+    #[test]
+    fn leaves_out_unknown_titles() {
+        let registry = get_mock_registry();
+        let json = tinyjson::JsonValue::from(registry.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(json.matches("\"title\"").count(), 1);
+
+        let legacy =
+            r#"{ "days": [{ "day": "03", "title": null, "part_1": true, "part_2": false }] }"#;
+        assert_eq!(
+            Registry::try_from(legacy.to_string()).unwrap().days,
+            registry.days[1..]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_entries() {
+        let json = r#"{ "days": [{ "day": "01", "title": null }] }"#.to_string();
+        Registry::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn registers_days_in_order() {
+        let mut registry = get_mock_registry();
+        registry.register(day!(2), Some("Gift Shop".into()));
+        assert_eq!(registry.days.len(), 3);
+        assert_eq!(registry.days[1].day, day!(2));
+        assert!(!registry.days[1].part_1);
+    }
+
+    #[test]
+    fn keeps_parts_when_reregistering() {
+        let mut registry = get_mock_registry();
+        registry.register(day!(1), None);
+        let entry = registry.get(day!(1)).unwrap();
+        assert_eq!(entry.title, Some("Secret Entrance".into()));
+        assert!(entry.part_1 && entry.part_2);
+    }
+
+    #[test]
+    fn sets_parts() {
+        let mut registry = get_mock_registry();
+        assert!(registry.set_parts(day!(3), true, true));
+        assert!(!registry.set_parts(day!(3), true, true));
+        assert!(!registry.set_parts(day!(2), true, true));
+    }
+}
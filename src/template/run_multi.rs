@@ -4,12 +4,21 @@ use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
 
 use super::{
     all_days,
+    registry::Registry,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    part: Option<u8>,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
+    let mut registry = Registry::read_from_file();
+    let mut registry_changed = false;
+
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
@@ -24,16 +33,34 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            // skip command invocation for days that have not been scaffolded yet.
+            let Some(entry) = registry.get(day).cloned() else {
+                println!("Not solved.");
+                return;
+            };
+
+            let output = child_commands::run_solution(day, is_timed, is_release, part).unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
             } else {
                 let val = child_commands::parse_exec_time(&output, day);
                 timings.push(val);
+
+                // parts that were not run keep their registered state.
+                let [part_1, part_2] = child_commands::parse_solved_parts(&output);
+                registry_changed |= registry.set_parts(
+                    day,
+                    part_1.unwrap_or(entry.part_1),
+                    part_2.unwrap_or(entry.part_2),
+                );
             }
         });
 
+    if registry_changed && let Err(e) = registry.store_file() {
+        eprintln!("Failed to update the registry: {e}");
+    }
+
     if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
@@ -59,30 +86,24 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{Day, timings::parse_duration_nanos};
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
         process::{Command, Stdio},
         thread,
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        part: Option<u8>,
+    ) -> Result<Vec<String>, Error> {
         let day_padded = day.to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
@@ -90,12 +111,22 @@ pub mod child_commands {
             args.push("--release");
         }
 
+        #[cfg(feature = "encrypted-inputs")]
+        args.extend(["--features", "encrypted-inputs"]);
+
+        args.push("--");
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        let part_str = part.map(|part| part.to_string());
+        if let Some(part_str) = &part_str {
+            args.push("--part");
+            args.push(part_str);
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -164,12 +195,24 @@ pub mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Returns for each part whether it produced a result, or `None` if it was not run.
+    pub fn parse_solved_parts(output: &[String]) -> [Option<bool>; 2] {
+        let mut parts = [None, None];
+
+        for line in output {
+            // intermediate results are overwritten with a carriage return.
+            let line = line.rsplit('\r').next().unwrap_or_default();
+            for (i, part) in parts.iter_mut().enumerate() {
+                if line.starts_with(&format!("Part {}:", i + 1)) {
+                    *part = Some(!line.contains('✖'));
+                }
+            }
+        }
+
+        parts
     }
 
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
@@ -179,12 +222,7 @@ pub mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = parse_duration_nanos(str_timing)?;
 
         Some((str_timing, parsed_timing))
     }
@@ -205,7 +243,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_solved_parts};
 
         use crate::day;
 
@@ -239,6 +277,19 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_solved_parts() {
+            let res = parse_solved_parts(&[
+                "Part 1: 3\rPart 1: 3 (1.0ms)".into(),
+                "Part 2: ✖\rPart 2: ✖             ".into(),
+            ]);
+            assert_eq!(res, [Some(true), Some(false)]);
+            assert_eq!(
+                parse_solved_parts(&["Part 2: 6".into()]),
+                [None, Some(true)]
+            );
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
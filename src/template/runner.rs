@@ -6,30 +6,74 @@ use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::memo::{self, MemoStats};
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::answers::Answers;
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, Input, aoc_cli};
+
+pub use crate::template::scaling::{Generator, require_generator, run_scaled};
+
+/// Reads the input selected by the `--input` / `--example` flags, exiting on failure.
+pub fn read_input(day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    let input = Input::from_args(&args).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    });
+
+    input.read(day).unwrap_or_else(|e| {
+        eprintln!("Failed to read input: {e}");
+        process::exit(1);
+    })
+}
+
+/// Returns the part selected with `--part 1|2`, or `None` if every part should run.
+pub fn get_selected_part() -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--part")?;
+
+    match args.get(index + 1).and_then(|x| x.parse::<u8>().ok()) {
+        Some(part @ (1 | 2)) => Some(part),
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --part 1");
+            process::exit(1);
+        }
+    }
+}
 
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
+    let (result, duration, samples, memo_stats) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
+    if is_timed() {
+        print_memo_stats(&memo_stats);
+    }
+
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Whether the solution is benched, i.e. run with `--time`.
+fn is_timed() -> bool {
+    env::args().any(|x| x == "--time")
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Also returns the statistics of the memoization caches used by the first execution.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Vec<MemoStats>) {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -38,16 +82,20 @@ fn run_timed<I: Copy, T>(
         func(input)
     };
     let base_time = timer.elapsed();
+    let memo_stats = memo::take_stats();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if is_timed() {
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    // Only report the caches of the first run, the benchmark repeats the same lookups.
+    let _ = memo::take_stats();
+
+    (result, run.0, run.1, memo_stats)
 }
 
 fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
@@ -90,6 +138,12 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
+fn print_memo_stats(memo_stats: &[MemoStats]) {
+    for stats in memo_stats {
+        println!("  {ANSI_ITALIC}memo {stats}{ANSI_RESET}");
+    }
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -155,6 +209,11 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    if Input::from_args(&args).is_ok_and(|input| input != Input::Puzzle) {
+        eprintln!("Refusing to submit a result that was not computed from the puzzle input.");
+        return None;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!(
             "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
@@ -162,6 +221,12 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
+    let mut answers = Answers::read_from_file();
+    answers.record(day, part, result.to_string());
+    if let Err(e) = answers.store_file() {
+        eprintln!("Failed to record the submitted answer: {e}");
+    }
+
     println!("Submitting result via aoc-cli...");
     Some(aoc_cli::submit(day, part, &result.to_string()))
 }
@@ -0,0 +1,496 @@
+/// Module that benchmarks a solution part on generated inputs of increasing size and
+/// fits the measured times against common complexity curves.
+///
+/// Days opt in by declaring an input generator, see [`solution!`](crate::solution).
+use std::{collections::HashMap, fmt::Display, fs, io::Error, process, time::Instant};
+use tinyjson::JsonValue;
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, data_dir};
+
+static SCALING_DIR_NAME: &str = "scaling";
+
+/// Number of input sizes to measure, each one double the size of the previous one.
+const SCALE_STEPS: u32 = 8;
+/// The smallest input size passed to a generator.
+const BASE_SIZE: usize = 16;
+
+/// Fits whose error is within this distance of the smallest one are considered equally
+/// good, the one whose slope is closest to 1 wins among them.
+const RMSE_TOLERANCE: f64 = 0.05;
+
+/// Generates a puzzle input of the given size, e.g. a number of lines or points.
+pub type Generator = fn(usize) -> String;
+
+/// A small deterministic pseudo-random number generator (xorshift64*) for input generators.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Returns a number in `range`, with a negligible bias for small ranges.
+    /// Panics if the range is empty.
+    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    pub fn range(&mut self, range: std::ops::Range<i64>) -> i64 {
+        assert!(
+            !range.is_empty(),
+            "cannot pick a number from the empty range {range:?}"
+        );
+        let span = range.end.abs_diff(range.start);
+        range.start.wrapping_add((self.next_u64() % span) as i64)
+    }
+}
+
+/// Dimensions of the rendered ASCII chart.
+const CHART_WIDTH: usize = 56;
+const CHART_HEIGHT: usize = 14;
+
+/// A complexity model that a series of measurements can be fitted against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Model {
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+    Exponential,
+}
+
+impl Model {
+    pub const ALL: [Model; 5] = [
+        Model::Linear,
+        Model::Linearithmic,
+        Model::Quadratic,
+        Model::Cubic,
+        Model::Exponential,
+    ];
+
+    /// Natural logarithm of the model function at `n`.
+    /// Models are compared in log space so that `2^n` does not overflow for large inputs.
+    fn ln_f(self, n: f64) -> f64 {
+        let n = n.max(1.0);
+        match self {
+            Model::Linear => n.ln(),
+            Model::Linearithmic => n.ln() + n.log2().max(1.0).ln(),
+            Model::Quadratic => 2.0 * n.ln(),
+            Model::Cubic => 3.0 * n.ln(),
+            Model::Exponential => n * std::f64::consts::LN_2,
+        }
+    }
+}
+
+impl Display for Model {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Model::Linear => "O(n)",
+            Model::Linearithmic => "O(n log n)",
+            Model::Quadratic => "O(n²)",
+            Model::Cubic => "O(n³)",
+            Model::Exponential => "O(2^n)",
+        })
+    }
+}
+
+/// A single measurement: the average execution time of a part for a generated input of `size`.
+#[derive(Clone, Copy, Debug)]
+pub struct Sample {
+    pub size: usize,
+    pub nanos: f64,
+}
+
+/// The result of fitting `t(n) = c * f(n)^slope` to a series of samples. A slope near 1
+/// means the model describes the growth, above or below 1 that it grows faster or slower.
+#[derive(Clone, Copy, Debug)]
+pub struct Fit {
+    pub model: Model,
+    /// Natural logarithm of the constant factor `c`.
+    pub ln_c: f64,
+    pub slope: f64,
+    /// Root mean square error of the fit in log space.
+    pub rmse: f64,
+}
+
+impl Fit {
+    /// Predicted execution time in nanoseconds for an input of size `n`.
+    pub fn predict(&self, n: f64) -> f64 {
+        (self.ln_c + self.slope * self.model.ln_f(n)).exp()
+    }
+}
+
+/// Least squares fit of `ln t = ln c + slope * ln f(n)`.
+fn fit_model(model: Model, samples: &[&Sample]) -> Fit {
+    let len = samples.len() as f64;
+    let xs: Vec<f64> = samples.iter().map(|s| model.ln_f(s.size as f64)).collect();
+    let ys: Vec<f64> = samples.iter().map(|s| s.nanos.ln()).collect();
+    let mean_x = xs.iter().sum::<f64>() / len;
+    let mean_y = ys.iter().sum::<f64>() / len;
+
+    let covariance: f64 = xs
+        .iter()
+        .zip(&ys)
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = xs.iter().map(|x| (x - mean_x).powi(2)).sum();
+    let slope = if variance > 0.0 {
+        covariance / variance
+    } else {
+        1.0
+    };
+    let ln_c = mean_y - slope * mean_x;
+
+    let rmse = (xs
+        .iter()
+        .zip(&ys)
+        .map(|(x, y)| (y - ln_c - slope * x).powi(2))
+        .sum::<f64>()
+        / len)
+        .sqrt();
+
+    Fit {
+        model,
+        ln_c,
+        slope,
+        rmse,
+    }
+}
+
+/// Fits every model to the samples and returns the fits ordered from best to worst.
+/// The polynomial models fit equally well once their slope is free, so among the fits
+/// with the smallest error the one that needs the slope closest to 1 is best.
+/// Returns an empty list if fewer than two samples are present.
+pub fn fit_models(samples: &[Sample]) -> Vec<Fit> {
+    let samples: Vec<&Sample> = samples.iter().filter(|s| s.nanos > 0.0).collect();
+
+    if samples.len() < 2 {
+        return vec![];
+    }
+
+    let mut fits: Vec<Fit> = Model::ALL
+        .iter()
+        .map(|&model| fit_model(model, &samples))
+        .collect();
+
+    let min_rmse = fits
+        .iter()
+        .map(|fit| fit.rmse)
+        .fold(f64::INFINITY, f64::min);
+    fits.sort_by(|a, b| {
+        let is_close = |fit: &Fit| fit.rmse <= min_rmse + RMSE_TOLERANCE;
+        is_close(b)
+            .cmp(&is_close(a))
+            .then((a.slope - 1.0).abs().total_cmp(&(b.slope - 1.0).abs()))
+    });
+    fits
+}
+
+/// Returns the input sizes to benchmark, doubling from [`BASE_SIZE`].
+fn get_sizes() -> Vec<usize> {
+    (0..SCALE_STEPS).map(|shift| BASE_SIZE << shift).collect()
+}
+
+/// Returns the generator of a day, exiting if the day doesn't declare one.
+pub fn require_generator(day: Day, generator: Option<Generator>) -> Generator {
+    generator.unwrap_or_else(|| {
+        eprintln!(
+            "Day {day} does not declare an input generator, which `--scale` needs. \
+            Declare one with `solution!({}, generator = generate_input)`.",
+            day.into_inner()
+        );
+        process::exit(1);
+    })
+}
+
+/// Runs a solution part once on `input` and then repeatedly for roughly `budget_nanos`.
+/// Returns `None` if the solution did not produce a result.
+fn measure<T>(func: &impl Fn(&str) -> Option<T>, input: &str, budget_nanos: u128) -> Option<f64> {
+    let timer = Instant::now();
+    func(input)?;
+    let base_nanos = timer.elapsed().as_nanos().max(10);
+
+    let iterations = (budget_nanos / base_nanos).clamp(1, 1000);
+
+    let timer = Instant::now();
+    for _ in 0..iterations {
+        std::hint::black_box(func(std::hint::black_box(input)));
+    }
+
+    Some(timer.elapsed().as_nanos() as f64 / iterations as f64)
+}
+
+/// Benchmarks a solution part on generated inputs of increasing size, prints the best
+/// matching complexity model alongside a chart and stores the samples in `data/scaling`.
+pub fn run_scaled<T>(func: impl Fn(&str) -> Option<T>, generator: Generator, day: Day, part: u8) {
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET} {ANSI_ITALIC}scaling{ANSI_RESET}");
+
+    let samples: Vec<Sample> = get_sizes()
+        .into_iter()
+        .filter_map(|size| {
+            let input = generator(size);
+            match measure(&func, &input, 200_000_000) {
+                Some(nanos) => {
+                    println!(
+                        "  n = {size:>6}: {ANSI_BOLD}{:.1?}{ANSI_RESET}",
+                        to_duration(nanos)
+                    );
+                    Some(Sample { size, nanos })
+                }
+                None => {
+                    println!("  n = {size:>6}: ✖");
+                    None
+                }
+            }
+        })
+        .collect();
+
+    let fits = fit_models(&samples);
+
+    let Some(best) = fits.first() else {
+        println!("Not enough samples to fit a complexity model.");
+        return;
+    };
+
+    println!();
+    println!(
+        "Best fit: {ANSI_BOLD}{}{ANSI_RESET} {ANSI_ITALIC}(slope {:.2}, rmse {:.3}){ANSI_RESET}",
+        best.model, best.slope, best.rmse
+    );
+    for fit in fits.iter().skip(1) {
+        println!(
+            "          {} {ANSI_ITALIC}(slope {:.2}, rmse {:.3}){ANSI_RESET}",
+            fit.model, fit.slope, fit.rmse
+        );
+    }
+    println!();
+    println!("{}", render_chart(&samples, best));
+
+    match store_file(day, part, &samples, best) {
+        Ok(path) => println!("Stored scaling results to \"{path}\"."),
+        Err(e) => eprintln!("Failed to store scaling results: {e}"),
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_duration(nanos: f64) -> std::time::Duration {
+    std::time::Duration::from_nanos(nanos as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders the samples (`*`) and the fitted curve (`·`) on log-log axes.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn render_chart(samples: &[Sample], fit: &Fit) -> String {
+    let mut canvas = vec![vec![' '; CHART_WIDTH]; CHART_HEIGHT];
+
+    let min_x = samples.iter().map(|s| s.size).min().unwrap_or(1).max(1) as f64;
+    let max_x = samples.iter().map(|s| s.size).max().unwrap_or(1).max(1) as f64;
+
+    let predictions: Vec<f64> = (0..CHART_WIDTH)
+        .map(|col| fit.predict(lerp_ln(min_x, max_x, col as f64 / (CHART_WIDTH - 1) as f64)))
+        .collect();
+
+    let min_y = samples
+        .iter()
+        .map(|s| s.nanos)
+        .chain(predictions.iter().copied())
+        .fold(f64::INFINITY, f64::min)
+        .max(1.0);
+    let max_y = samples
+        .iter()
+        .map(|s| s.nanos)
+        .chain(predictions.iter().copied())
+        .fold(f64::NEG_INFINITY, f64::max)
+        .max(min_y);
+
+    let to_row = |y: f64| {
+        let t = inv_lerp_ln(min_y, max_y, y.clamp(min_y, max_y));
+        CHART_HEIGHT - 1 - (t * (CHART_HEIGHT - 1) as f64).round() as usize
+    };
+
+    for (col, &y) in predictions.iter().enumerate() {
+        canvas[to_row(y)][col] = '·';
+    }
+
+    for sample in samples {
+        let t = inv_lerp_ln(min_x, max_x, sample.size as f64);
+        let col = (t * (CHART_WIDTH - 1) as f64).round() as usize;
+        canvas[to_row(sample.nanos)][col] = '*';
+    }
+
+    let top_label = format!("{:.1?}", to_duration(max_y));
+    let bottom_label = format!("{:.1?}", to_duration(min_y));
+    let label_width = top_label.chars().count().max(bottom_label.chars().count());
+
+    let mut lines: Vec<String> = canvas
+        .iter()
+        .enumerate()
+        .map(|(row, cells)| {
+            let label = match row {
+                0 => top_label.as_str(),
+                r if r == CHART_HEIGHT - 1 => bottom_label.as_str(),
+                _ => "",
+            };
+            format!(
+                "{label:>label_width$} │{}",
+                cells.iter().collect::<String>()
+            )
+        })
+        .collect();
+
+    lines.push(format!("{:>label_width$} └{}", "", "─".repeat(CHART_WIDTH)));
+
+    let min_label = format!("n = {min_x}");
+    let max_label = format!("n = {max_x}");
+    let padding = CHART_WIDTH.saturating_sub(min_label.len() + max_label.len());
+    lines.push(format!(
+        "{:>label_width$}  {min_label}{}{max_label}",
+        "",
+        " ".repeat(padding)
+    ));
+
+    lines.join("\n")
+}
+
+fn lerp_ln(min: f64, max: f64, t: f64) -> f64 {
+    (min.ln() + (max.ln() - min.ln()) * t).exp()
+}
+
+fn inv_lerp_ln(min: f64, max: f64, value: f64) -> f64 {
+    if max <= min {
+        return 0.0;
+    }
+    (value.ln() - min.ln()) / (max.ln() - min.ln())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Dehydrate scaling samples and the best fit to `data/scaling/DD-P.json`.
+fn store_file(day: Day, part: u8, samples: &[Sample], fit: &Fit) -> Result<String, Error> {
+    let dir = data_dir().join(SCALING_DIR_NAME);
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{day}-{part}.json"));
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("day".into(), JsonValue::String(day.to_string()));
+    map.insert("part".into(), JsonValue::Number(f64::from(part)));
+    map.insert("model".into(), JsonValue::String(fit.model.to_string()));
+    map.insert("slope".into(), JsonValue::Number(fit.slope));
+    map.insert(
+        "samples".into(),
+        JsonValue::Array(
+            samples
+                .iter()
+                .map(|sample| {
+                    let mut map: HashMap<String, JsonValue> = HashMap::new();
+                    map.insert("size".into(), JsonValue::Number(sample.size as f64));
+                    map.insert("nanos".into(), JsonValue::Number(sample.nanos));
+                    JsonValue::Object(map)
+                })
+                .collect(),
+        ),
+    );
+
+    let mut file = fs::File::create(&path)?;
+    JsonValue::Object(map).format_to(&mut file)?;
+    Ok(path.display().to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Model, Rng, Sample, fit_models, get_sizes};
+
+    fn samples_for(f: impl Fn(f64) -> f64) -> Vec<Sample> {
+        [16, 32, 64, 128, 256, 512, 1024]
+            .iter()
+            .map(|&size| Sample {
+                size,
+                nanos: f(size as f64),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fits_linear_samples() {
+        let fits = fit_models(&samples_for(|n| 40.0 * n));
+        assert_eq!(fits[0].model, Model::Linear);
+    }
+
+    #[test]
+    fn fits_linearithmic_samples() {
+        let fits = fit_models(&samples_for(|n| 3.0 * n * n.log2()));
+        assert_eq!(fits[0].model, Model::Linearithmic);
+    }
+
+    #[test]
+    fn fits_quadratic_samples() {
+        let fits = fit_models(&samples_for(|n| 0.5 * n * n));
+        assert_eq!(fits[0].model, Model::Quadratic);
+        assert!((fits[0].slope - 1.0).abs() < 1e-9);
+        assert!((fits[0].predict(100.0) - 5000.0).abs() < 1e-6);
+
+        // The linear model needs twice the slope for the same samples.
+        let linear = fits.iter().find(|fit| fit.model == Model::Linear).unwrap();
+        assert!((linear.slope - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fits_intercept_and_slope() {
+        // Between the models, with a constant overhead that a fixed slope would misjudge.
+        let fits = fit_models(&samples_for(|n| 1000.0 * n.powf(1.5)));
+        let linear = fits.iter().find(|fit| fit.model == Model::Linear).unwrap();
+        assert!((linear.slope - 1.5).abs() < 1e-9);
+        assert!((linear.ln_c - 1000_f64.ln()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fits_cubic_samples() {
+        let fits = fit_models(&samples_for(|n| n * n * n));
+        assert_eq!(fits[0].model, Model::Cubic);
+    }
+
+    #[test]
+    fn fits_exponential_samples() {
+        let samples: Vec<Sample> = (4..12)
+            .map(|size| Sample {
+                size,
+                nanos: 2_f64.powi(size as i32),
+            })
+            .collect();
+        let fits = fit_models(&samples);
+        assert_eq!(fits[0].model, Model::Exponential);
+    }
+
+    #[test]
+    fn requires_two_samples() {
+        assert!(fit_models(&samples_for(|n| n)[..1]).is_empty());
+    }
+
+    #[test]
+    fn doubles_sizes() {
+        assert_eq!(get_sizes(), vec![16, 32, 64, 128, 256, 512, 1024, 2048]);
+    }
+
+    #[test]
+    fn generates_numbers_in_range() {
+        let mut rng = Rng::new(7);
+        let numbers: Vec<i64> = (0..1000).map(|_| rng.range(-3..4)).collect();
+        assert!(numbers.iter().all(|n| (-3..4).contains(n)));
+        assert!((-3..4).all(|n| numbers.contains(&n)));
+        assert_eq!(Rng::new(7).next_u64(), Rng::new(7).next_u64());
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn rejects_empty_ranges() {
+        Rng::new(7).range(3..3);
+    }
+}
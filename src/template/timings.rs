@@ -1,9 +1,28 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{Day, data_dir};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+fn get_timings_path() -> PathBuf {
+    data_dir().join(TIMINGS_FILE_NAME)
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Parses a formatted [`std::time::Duration`] such as `74.13ms` into nanoseconds.
+pub fn parse_duration_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -25,13 +44,13 @@ impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(get_timings_path())?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+        fs::read_to_string(get_timings_path())
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -51,10 +70,49 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
+    /// Merge the timings of a single part into `self`, keeping the stored timing of the other part.
+    pub fn merge_part(&self, new: &Self, part: u8) -> Self {
+        let data = new
+            .data
+            .iter()
+            .map(|timing| {
+                let mut merged = self
+                    .data
+                    .iter()
+                    .find(|t| t.day == timing.day)
+                    .cloned()
+                    .unwrap_or(Timing {
+                        day: timing.day,
+                        part_1: None,
+                        part_2: None,
+                        total_nanos: 0_f64,
+                    });
+
+                let kept = if part == 1 {
+                    merged.part_1.clone_from(&timing.part_1);
+                    &merged.part_2
+                } else {
+                    merged.part_2.clone_from(&timing.part_2);
+                    &merged.part_1
+                };
+
+                merged.total_nanos = timing.total_nanos
+                    + kept
+                        .as_deref()
+                        .and_then(parse_duration_nanos)
+                        .unwrap_or_default();
+
+                merged
+            })
+            .collect();
+
+        self.merge(&Timings { data })
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
@@ -65,6 +123,16 @@ impl Timings {
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    pub fn is_part_complete(&self, day: Day, part: u8) -> bool {
+        self.data.iter().any(|t| {
+            t.day == day
+                && match part {
+                    1 => t.part_1.is_some(),
+                    _ => t.part_2.is_some(),
+                }
+        })
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -285,7 +353,7 @@ mod tests {
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -299,7 +367,7 @@ mod tests {
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -313,7 +381,7 @@ mod tests {
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -381,4 +449,66 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
     }
+
+    mod merge_part {
+        use crate::{
+            day,
+            template::timings::{Timing, Timings},
+        };
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn keeps_other_part() {
+            let timings = get_mock_timings();
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(2),
+                    part_1: None,
+                    part_2: Some("5ms".into()),
+                    total_nanos: 5e+6,
+                }],
+            };
+            let merged = timings.merge_part(&other, 2);
+
+            assert_eq!(merged.data.len(), 3);
+            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(merged.data[1].part_1, Some("30ms".into()));
+            assert_eq!(merged.data[1].part_2, Some("5ms".into()));
+            assert_eq!(merged.data[1].total_nanos, 35e+6);
+        }
+
+        #[test]
+        fn handles_new_days() {
+            let timings = get_mock_timings();
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(3),
+                    part_1: Some("1ms".into()),
+                    part_2: None,
+                    total_nanos: 1e+6,
+                }],
+            };
+            let merged = timings.merge_part(&other, 1);
+
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[2].day, day!(3));
+            assert_eq!(merged.data[2].part_1, Some("1ms".into()));
+            assert_eq!(merged.data[2].part_2, None);
+            assert_eq!(merged.data[2].total_nanos, 1e+6);
+        }
+    }
+
+    mod parse_duration_nanos {
+        use crate::template::timings::parse_duration_nanos;
+
+        #[test]
+        fn handles_all_units() {
+            assert_eq!(parse_duration_nanos("74.5ns"), Some(74.5));
+            assert_eq!(parse_duration_nanos("2µs"), Some(2000.0));
+            assert_eq!(parse_duration_nanos("1.5ms"), Some(1_500_000.0));
+            assert_eq!(parse_duration_nanos("3s"), Some(3e+9));
+            assert_eq!(parse_duration_nanos("-"), None);
+        }
+    }
 }